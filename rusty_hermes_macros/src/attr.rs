@@ -0,0 +1,243 @@
+//! Parsing of `#[hermes(...)]` attributes.
+
+use syn::ext::IdentExt;
+use syn::{Attribute, ExprPath, Field, Fields, Ident, LitStr, Token};
+
+/// Case conversion applied by `rename_all`, following serde's rule names.
+#[derive(Clone, Copy)]
+pub(crate) enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    fn parse(lit: &LitStr) -> syn::Result<Self> {
+        Ok(match lit.value().as_str() {
+            "lowercase" => RenameRule::Lower,
+            "UPPERCASE" => RenameRule::Upper,
+            "PascalCase" => RenameRule::Pascal,
+            "camelCase" => RenameRule::Camel,
+            "snake_case" => RenameRule::Snake,
+            "SCREAMING_SNAKE_CASE" => RenameRule::ScreamingSnake,
+            "kebab-case" => RenameRule::Kebab,
+            "SCREAMING-KEBAB-CASE" => RenameRule::ScreamingKebab,
+            other => {
+                return Err(syn::Error::new(
+                    lit.span(),
+                    format!("unknown rename rule `{other}`"),
+                ))
+            }
+        })
+    }
+
+    /// Renames a `PascalCase` variant name.
+    pub(crate) fn apply_to_variant(self, name: &str) -> String {
+        match self {
+            RenameRule::Lower => name.to_ascii_lowercase(),
+            RenameRule::Upper => name.to_ascii_uppercase(),
+            RenameRule::Pascal => name.to_owned(),
+            RenameRule::Camel => {
+                let mut chars = name.chars();
+                match chars.next() {
+                    Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+                    None => String::new(),
+                }
+            }
+            RenameRule::Snake
+            | RenameRule::ScreamingSnake
+            | RenameRule::Kebab
+            | RenameRule::ScreamingKebab => {
+                let mut snake = String::new();
+                for (i, ch) in name.char_indices() {
+                    if i > 0 && ch.is_uppercase() {
+                        snake.push('_');
+                    }
+                    snake.push(ch.to_ascii_lowercase());
+                }
+                self.rejoin_snake(&snake)
+            }
+        }
+    }
+
+    /// Renames a `snake_case` field name.
+    pub(crate) fn apply_to_field(self, name: &str) -> String {
+        match self {
+            RenameRule::Lower | RenameRule::Snake => name.to_owned(),
+            RenameRule::Upper => name.to_ascii_uppercase(),
+            RenameRule::Pascal | RenameRule::Camel => {
+                let mut out = String::new();
+                let mut capitalize = matches!(self, RenameRule::Pascal);
+                for ch in name.chars() {
+                    if ch == '_' {
+                        capitalize = !out.is_empty();
+                    } else if capitalize {
+                        out.push(ch.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        out.push(ch);
+                    }
+                }
+                out
+            }
+            RenameRule::ScreamingSnake | RenameRule::Kebab | RenameRule::ScreamingKebab => {
+                self.rejoin_snake(name)
+            }
+        }
+    }
+
+    /// Renames a `snake_case` name for the rules that keep word separators.
+    fn rejoin_snake(self, snake: &str) -> String {
+        match self {
+            RenameRule::ScreamingSnake => snake.to_ascii_uppercase(),
+            RenameRule::Kebab => snake.replace('_', "-"),
+            RenameRule::ScreamingKebab => snake.replace('_', "-").to_ascii_uppercase(),
+            _ => snake.to_owned(),
+        }
+    }
+}
+
+/// Attributes on the struct or enum itself.
+#[derive(Default)]
+pub(crate) struct ContainerAttrs {
+    /// Applied to field names of a struct, or variant names of an enum.
+    pub rename_all: Option<RenameRule>,
+    /// Stores an enum's variant name in this field of the object.
+    pub tag: Option<String>,
+}
+
+/// Attributes on an enum variant.
+#[derive(Default)]
+pub(crate) struct VariantAttrs {
+    pub rename: Option<String>,
+    /// Applied to the names of the variant's fields.
+    pub rename_all: Option<RenameRule>,
+}
+
+/// Attributes on a field.
+#[derive(Default)]
+pub(crate) struct FieldAttrs {
+    pub rename: Option<String>,
+    /// Value used by `FromJs` when the property is missing or `undefined`.
+    pub default: Option<FieldDefault>,
+}
+
+pub(crate) enum FieldDefault {
+    /// `#[hermes(default)]`: `Default::default()`.
+    Trait,
+    /// `#[hermes(default = "path")]`: calls the function at `path`.
+    Path(ExprPath),
+}
+
+impl ContainerAttrs {
+    pub(crate) fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut out = ContainerAttrs::default();
+        for_each_meta(attrs, |meta| {
+            if meta.path.is_ident("rename_all") {
+                out.rename_all = Some(RenameRule::parse(&meta.value()?.parse()?)?);
+            } else if meta.path.is_ident("tag") {
+                out.tag = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else {
+                return Err(meta.error("unknown container attribute"));
+            }
+            Ok(())
+        })?;
+        Ok(out)
+    }
+}
+
+impl VariantAttrs {
+    pub(crate) fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut out = VariantAttrs::default();
+        for_each_meta(attrs, |meta| {
+            if meta.path.is_ident("rename") {
+                out.rename = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("rename_all") {
+                out.rename_all = Some(RenameRule::parse(&meta.value()?.parse()?)?);
+            } else {
+                return Err(meta.error("unknown variant attribute"));
+            }
+            Ok(())
+        })?;
+        Ok(out)
+    }
+}
+
+impl FieldAttrs {
+    pub(crate) fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut out = FieldAttrs::default();
+        for_each_meta(attrs, |meta| {
+            if meta.path.is_ident("rename") {
+                out.rename = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("default") {
+                out.default = Some(if meta.input.peek(Token![=]) {
+                    FieldDefault::Path(meta.value()?.parse::<LitStr>()?.parse()?)
+                } else {
+                    FieldDefault::Trait
+                });
+            } else {
+                return Err(meta.error("unknown field attribute"));
+            }
+            Ok(())
+        })?;
+        Ok(out)
+    }
+}
+
+fn for_each_meta(
+    attrs: &[Attribute],
+    mut f: impl FnMut(syn::meta::ParseNestedMeta) -> syn::Result<()>,
+) -> syn::Result<()> {
+    for attr in attrs.iter().filter(|a| a.path().is_ident("hermes")) {
+        attr.parse_nested_meta(&mut f)?;
+    }
+    Ok(())
+}
+
+/// Rejects `#[hermes(default)]` on tuple fields, which are read by
+/// position and so are never missing.
+pub(crate) fn check_tuple_defaults(fields: &Fields) -> syn::Result<()> {
+    if let Fields::Unnamed(unnamed) = fields {
+        for field in &unnamed.unnamed {
+            reject_default(field, "`default` only applies to named fields")?;
+        }
+    }
+    Ok(())
+}
+
+/// Fails with `msg` if `field` is marked `#[hermes(default)]`.
+pub(crate) fn reject_default(field: &Field, msg: &str) -> syn::Result<()> {
+    match FieldAttrs::parse(&field.attrs)?.default {
+        Some(_) => Err(syn::Error::new_spanned(field, msg)),
+        None => Ok(()),
+    }
+}
+
+/// JS property name of a named field.
+pub(crate) fn field_key(ident: &Ident, attrs: &FieldAttrs, rule: Option<RenameRule>) -> String {
+    let name = ident.unraw().to_string();
+    match (&attrs.rename, rule) {
+        (Some(rename), _) => rename.clone(),
+        (None, Some(rule)) => rule.apply_to_field(&name),
+        (None, None) => name,
+    }
+}
+
+/// JS name of an enum variant.
+pub(crate) fn variant_name(
+    ident: &Ident,
+    attrs: &VariantAttrs,
+    rule: Option<RenameRule>,
+) -> String {
+    let name = ident.unraw().to_string();
+    match (&attrs.rename, rule) {
+        (Some(rename), _) => rename.clone(),
+        (None, Some(rule)) => rule.apply_to_variant(&name),
+        (None, None) => name,
+    }
+}
@@ -0,0 +1,203 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DataEnum, DeriveInput, Fields};
+
+use crate::attr::{self, ContainerAttrs, FieldAttrs, FieldDefault, RenameRule, VariantAttrs};
+
+pub(crate) fn expand(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let attrs = ContainerAttrs::parse(&input.attrs)?;
+    let ty_name = input.ident.to_string();
+    let body = match &input.data {
+        Data::Struct(data) => {
+            if attrs.tag.is_some() {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    "`tag` only applies to enums",
+                ));
+            }
+            construct(&data.fields, quote!(Self), attrs.rename_all)?
+        }
+        Data::Enum(data) => enum_body(data, &attrs, &ty_name)?,
+        Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "unions cannot be converted from JS",
+            ))
+        }
+    };
+
+    let ident = &input.ident;
+    let generics = crate::with_rt_lifetime(&input.generics, quote!(::rusty_hermes::FromJs<'__rt>));
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::rusty_hermes::FromJs<'__rt> for #ident #ty_generics #where_clause {
+            fn from_js(
+                __value: &::rusty_hermes::Value<'__rt>,
+            ) -> ::rusty_hermes::Result<Self> {
+                #body
+            }
+        }
+
+        impl #impl_generics ::rusty_hermes::FromJsArg<'__rt> for #ident #ty_generics #where_clause {
+            fn from_arg(
+                __value: &::rusty_hermes::Value<'__rt>,
+            ) -> ::rusty_hermes::Result<Self> {
+                ::rusty_hermes::FromJs::from_js(__value)
+            }
+        }
+    })
+}
+
+fn enum_body(data: &DataEnum, attrs: &ContainerAttrs, ty_name: &str) -> syn::Result<TokenStream> {
+    let mut unit_arms = Vec::new();
+    let mut payload_arms = Vec::new();
+    for variant in &data.variants {
+        let variant_attrs = VariantAttrs::parse(&variant.attrs)?;
+        let ident = &variant.ident;
+        let name = attr::variant_name(ident, &variant_attrs, attrs.rename_all);
+        match (&attrs.tag, &variant.fields) {
+            (_, Fields::Unit) => unit_arms.push(quote! {
+                #name => ::std::result::Result::Ok(Self::#ident),
+            }),
+            (tag, fields) => {
+                if tag.is_some() {
+                    crate::check_tagged_variant(variant)?;
+                }
+                let value = construct(fields, quote!(Self::#ident), variant_attrs.rename_all)?;
+                payload_arms.push(match tag {
+                    Some(_) => quote! {
+                        #name => { #value }
+                    },
+                    None => {
+                        let segment = format!(".{}", name);
+                        quote! {
+                            #name => {
+                                let __result: ::rusty_hermes::Result<Self> = (|| { #value })();
+                                __result.map_err(|__err| ::rusty_hermes::__private::at(__err, #segment))
+                            }
+                        }
+                    }
+                });
+            }
+        }
+    }
+
+    let unknown = quote! {
+        __name => ::std::result::Result::Err(
+            ::rusty_hermes::__private::unknown_variant(__name, #ty_name),
+        ),
+    };
+
+    Ok(match &attrs.tag {
+        Some(tag) => quote! {
+            let __obj = ::std::clone::Clone::clone(__value).into_object()?;
+            let __tag = __obj.get(#tag)?;
+            if __tag.is_undefined() {
+                return ::std::result::Result::Err(
+                    ::rusty_hermes::__private::missing_tag(#tag, #ty_name),
+                );
+            }
+            let __name = <::std::string::String as ::rusty_hermes::FromJs>::from_js(&__tag)?;
+            match __name.as_str() {
+                #(#unit_arms)*
+                #(#payload_arms)*
+                #unknown
+            }
+        },
+        None => quote! {
+            match __value {
+                ::rusty_hermes::Value::String(_) => {
+                    let __name = <::std::string::String as ::rusty_hermes::FromJs>::from_js(__value)?;
+                    match __name.as_str() {
+                        #(#unit_arms)*
+                        #unknown
+                    }
+                }
+                ::rusty_hermes::Value::Object(__obj) => {
+                    let (__name, __payload) =
+                        ::rusty_hermes::__private::external_variant(__obj, #ty_name)?;
+                    let __value = &__payload;
+                    match __name.as_str() {
+                        #(#payload_arms)*
+                        #unknown
+                    }
+                }
+                __other => ::std::result::Result::Err(
+                    ::rusty_hermes::__private::expected("string or object", __other),
+                ),
+            }
+        },
+    })
+}
+
+/// Statements building `path` from `__value`, evaluating to `Result<Self>`.
+fn construct(
+    fields: &Fields,
+    path: TokenStream,
+    rename_all: Option<RenameRule>,
+) -> syn::Result<TokenStream> {
+    attr::check_tuple_defaults(fields)?;
+    Ok(match fields {
+        Fields::Named(named) => {
+            let mut inits = Vec::new();
+            for field in &named.named {
+                let field_attrs = FieldAttrs::parse(&field.attrs)?;
+                let ident = field.ident.as_ref().expect("named field");
+                let key = attr::field_key(ident, &field_attrs, rename_all);
+                let segment = format!(".{}", key);
+                let convert = quote! {
+                    ::rusty_hermes::FromJs::from_js(&__field)
+                        .map_err(|__err| ::rusty_hermes::__private::at(__err, #segment))?
+                };
+                let value = match &field_attrs.default {
+                    None => convert,
+                    Some(default) => {
+                        let default = match default {
+                            FieldDefault::Trait => quote!(::std::default::Default::default()),
+                            FieldDefault::Path(path) => quote!(#path()),
+                        };
+                        quote! {
+                            if __field.is_undefined() { #default } else { #convert }
+                        }
+                    }
+                };
+                inits.push(quote! {
+                    #ident: {
+                        let __field = __obj.get(#key)?;
+                        #value
+                    },
+                });
+            }
+            quote! {
+                let __obj = ::std::clone::Clone::clone(__value).into_object()?;
+                ::std::result::Result::Ok(#path { #(#inits)* })
+            }
+        }
+        Fields::Unnamed(unnamed) if unnamed.unnamed.len() == 1 => quote! {
+            ::std::result::Result::Ok(#path(::rusty_hermes::FromJs::from_js(__value)?))
+        },
+        Fields::Unnamed(unnamed) => {
+            let len = unnamed.unnamed.len();
+            let indices = 0..len;
+            let segments = (0..len).map(|i| format!("[{}]", i));
+            quote! {
+                let __array = ::rusty_hermes::__private::tuple(__value, #len)?;
+                ::std::result::Result::Ok(#path(
+                    #(::rusty_hermes::FromJs::from_js(&__array.get(#indices)?)
+                        .map_err(|__err| ::rusty_hermes::__private::at(__err, #segments))?,)*
+                ))
+            }
+        }
+        Fields::Unit => quote! {
+            match __value {
+                ::rusty_hermes::Value::Null | ::rusty_hermes::Value::Undefined => {
+                    ::std::result::Result::Ok(#path)
+                }
+                __other => ::std::result::Result::Err(
+                    ::rusty_hermes::__private::expected("null", __other),
+                ),
+            }
+        },
+    })
+}
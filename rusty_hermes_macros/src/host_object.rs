@@ -0,0 +1,74 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, GenericParam};
+
+use crate::attr::{self, ContainerAttrs, FieldAttrs};
+
+pub(crate) fn expand(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let attrs = ContainerAttrs::parse(&input.attrs)?;
+    let fields = match &input.data {
+        Data::Struct(data) if attrs.tag.is_none() => match &data.fields {
+            Fields::Named(named) => &named.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    "`HostObject` can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "`HostObject` can only be derived for structs with named fields",
+            ))
+        }
+    };
+
+    let mut arms = Vec::new();
+    let mut keys = Vec::new();
+    for field in fields {
+        attr::reject_default(field, "`default` does not apply to `HostObject` fields")?;
+        let field_attrs = FieldAttrs::parse(&field.attrs)?;
+        let ident = field.ident.as_ref().expect("named field");
+        let key = attr::field_key(ident, &field_attrs, attrs.rename_all);
+        arms.push(quote! {
+            #key => ::rusty_hermes::IntoJs::into_js(::std::clone::Clone::clone(&self.#ident), __rt),
+        });
+        keys.push(key);
+    }
+
+    let ident = &input.ident;
+    let mut generics = input.generics.clone();
+    for param in &mut generics.params {
+        if let GenericParam::Type(ty) = param {
+            ty.bounds.push(syn::parse_quote!(::std::clone::Clone));
+            ty.bounds
+                .push(syn::parse_quote!(for<'__rt> ::rusty_hermes::IntoJs<'__rt>));
+            ty.bounds.push(syn::parse_quote!('static));
+        }
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::rusty_hermes::HostObject for #ident #ty_generics #where_clause {
+            fn get<'__rt>(
+                &self,
+                __rt: &'__rt ::rusty_hermes::Runtime,
+                __name: &::rusty_hermes::PropNameId<'__rt>,
+            ) -> ::rusty_hermes::Result<::rusty_hermes::Value<'__rt>> {
+                match __name.to_rust_string()?.as_str() {
+                    #(#arms)*
+                    _ => ::std::result::Result::Ok(::rusty_hermes::Value::Undefined),
+                }
+            }
+
+            fn property_names<'__rt>(
+                &self,
+                __rt: &'__rt ::rusty_hermes::Runtime,
+            ) -> ::rusty_hermes::Result<::std::vec::Vec<::rusty_hermes::PropNameId<'__rt>>> {
+                ::std::result::Result::Ok(::std::vec![
+                    #(::rusty_hermes::PropNameId::new(__rt, #keys)?,)*
+                ])
+            }
+        }
+    })
+}
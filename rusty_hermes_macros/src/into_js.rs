@@ -0,0 +1,165 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DataEnum, DeriveInput, Fields, Ident};
+
+use crate::attr::{self, ContainerAttrs, FieldAttrs, RenameRule, VariantAttrs};
+
+pub(crate) fn expand(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let attrs = ContainerAttrs::parse(&input.attrs)?;
+    let body = match &input.data {
+        Data::Struct(data) => {
+            if attrs.tag.is_some() {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    "`tag` only applies to enums",
+                ));
+            }
+            let bindings = crate::field_bindings(data.fields.len());
+            let pattern = fields_pattern(&data.fields, &bindings);
+            let value = fields_value(&data.fields, &bindings, attrs.rename_all)?;
+            quote! {
+                let Self #pattern = self;
+                #value
+            }
+        }
+        Data::Enum(data) => enum_body(data, &attrs)?,
+        Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "unions cannot be converted to JS",
+            ))
+        }
+    };
+
+    let ident = &input.ident;
+    let generics = crate::with_rt_lifetime(&input.generics, quote!(::rusty_hermes::IntoJs<'__rt>));
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::rusty_hermes::IntoJs<'__rt> for #ident #ty_generics #where_clause {
+            fn into_js(
+                self,
+                __rt: &'__rt ::rusty_hermes::Runtime,
+            ) -> ::rusty_hermes::Result<::rusty_hermes::Value<'__rt>> {
+                #body
+            }
+        }
+
+        impl #impl_generics ::rusty_hermes::IntoJsRet<'__rt> for #ident #ty_generics #where_clause {
+            fn into_ret(
+                self,
+                __rt: &'__rt ::rusty_hermes::Runtime,
+            ) -> ::rusty_hermes::Result<::rusty_hermes::Value<'__rt>> {
+                ::rusty_hermes::IntoJs::into_js(self, __rt)
+            }
+        }
+    })
+}
+
+fn enum_body(data: &DataEnum, attrs: &ContainerAttrs) -> syn::Result<TokenStream> {
+    let mut arms = Vec::new();
+    for variant in &data.variants {
+        let variant_attrs = VariantAttrs::parse(&variant.attrs)?;
+        let ident = &variant.ident;
+        let name = attr::variant_name(ident, &variant_attrs, attrs.rename_all);
+        let bindings = crate::field_bindings(variant.fields.len());
+        let pattern = fields_pattern(&variant.fields, &bindings);
+
+        let value = match (&attrs.tag, &variant.fields) {
+            (None, Fields::Unit) => quote! {
+                ::rusty_hermes::IntoJs::into_js(#name, __rt)
+            },
+            (None, fields) => {
+                let payload = fields_value(fields, &bindings, variant_attrs.rename_all)?;
+                quote! {
+                    let __payload: ::rusty_hermes::Result<_> = { #payload };
+                    let __payload = __payload?;
+                    let __obj = ::rusty_hermes::Object::new(__rt)?;
+                    __obj.set(#name, __payload)?;
+                    ::std::result::Result::Ok(::rusty_hermes::Value::Object(__obj))
+                }
+            }
+            (Some(tag), fields) => {
+                crate::check_tagged_variant(variant)?;
+                let tag_field = quote! {
+                    __obj.set(#tag, ::rusty_hermes::IntoJs::into_js(#name, __rt)?)?;
+                };
+                named_object(fields, &bindings, variant_attrs.rename_all, tag_field)?
+            }
+        };
+        arms.push(quote! {
+            Self::#ident #pattern => { #value }
+        });
+    }
+    Ok(quote! {
+        match self {
+            #(#arms)*
+        }
+    })
+}
+
+/// Pattern binding each field of a struct or variant to `bindings`.
+fn fields_pattern(fields: &Fields, bindings: &[Ident]) -> TokenStream {
+    match fields {
+        Fields::Named(named) => {
+            let idents = named.named.iter().map(|f| &f.ident);
+            quote!({ #(#idents: #bindings),* })
+        }
+        Fields::Unnamed(_) => quote!((#(#bindings),*)),
+        Fields::Unit => quote!(),
+    }
+}
+
+/// Statements converting the bound fields, evaluating to the JS value.
+fn fields_value(
+    fields: &Fields,
+    bindings: &[Ident],
+    rename_all: Option<RenameRule>,
+) -> syn::Result<TokenStream> {
+    // `default` on named fields is left to a `FromJs` derive on the same
+    // type; tuple fields have no use for it in either direction.
+    attr::check_tuple_defaults(fields)?;
+    Ok(match fields {
+        Fields::Named(_) => named_object(fields, bindings, rename_all, quote!())?,
+        Fields::Unnamed(unnamed) if unnamed.unnamed.len() == 1 => quote! {
+            ::rusty_hermes::IntoJs::into_js(#(#bindings)*, __rt)
+        },
+        Fields::Unnamed(_) => {
+            let len = bindings.len();
+            let indices = 0..len;
+            quote! {
+                let __array = ::rusty_hermes::Array::new(__rt, #len)?;
+                #(__array.set(#indices, ::rusty_hermes::IntoJs::into_js(#bindings, __rt)?)?;)*
+                ::std::result::Result::Ok(::rusty_hermes::Value::from(__array))
+            }
+        }
+        Fields::Unit => quote! {
+            ::std::result::Result::Ok(::rusty_hermes::Value::Null)
+        },
+    })
+}
+
+/// Statements building an object from named fields, after running
+/// `prelude` with `__obj` in scope.
+fn named_object(
+    fields: &Fields,
+    bindings: &[Ident],
+    rename_all: Option<RenameRule>,
+    prelude: TokenStream,
+) -> syn::Result<TokenStream> {
+    let mut sets = Vec::new();
+    for (field, binding) in fields.iter().zip(bindings) {
+        let field_attrs = FieldAttrs::parse(&field.attrs)?;
+        let ident = field.ident.as_ref().expect("named field");
+        let key = attr::field_key(ident, &field_attrs, rename_all);
+        sets.push(quote! {
+            __obj.set(#key, ::rusty_hermes::IntoJs::into_js(#binding, __rt)?)?;
+        });
+    }
+    Ok(quote! {
+        let __obj = ::rusty_hermes::Object::new(__rt)?;
+        #prelude
+        #(#sets)*
+        ::std::result::Result::Ok(::rusty_hermes::Value::Object(__obj))
+    })
+}
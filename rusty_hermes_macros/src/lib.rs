@@ -0,0 +1,128 @@
+//! Derive macros for [`rusty_hermes`](https://docs.rs/rusty_hermes).
+//!
+//! Use them through the re-exports in `rusty_hermes` rather than depending
+//! on this crate directly; the generated code refers to `::rusty_hermes`.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use syn::{parse_macro_input, parse_quote, DeriveInput, GenericParam, Generics, ItemFn};
+
+mod attr;
+mod from_js;
+mod host_object;
+mod into_js;
+mod op;
+
+/// Derives `IntoJs` and `IntoJsRet`.
+///
+/// Structs with named fields become objects, newtype structs convert as
+/// their field, tuple structs become arrays and unit structs become `null`.
+///
+/// Enums are externally tagged by default: unit variants become their name
+/// as a string, other variants `{ "Name": payload }`. With
+/// `#[hermes(tag = "...")]` unit and struct variants become a single object
+/// holding the variant name in the tag field.
+///
+/// Names can be changed with `#[hermes(rename = "...")]` on fields and
+/// variants, and `#[hermes(rename_all = "...")]` on the container (fields of
+/// a struct, variants of an enum) or on a variant (its fields), using serde's
+/// rule names such as `"camelCase"` or `"lowercase"`.
+#[proc_macro_derive(IntoJs, attributes(hermes))]
+pub fn derive_into_js(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    into_js::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `FromJs` and `FromJsArg`, reading the representation produced by
+/// the `IntoJs` derive with the same attributes.
+///
+/// A named field marked `#[hermes(default)]` reads a missing or `undefined`
+/// property as `Default::default()`; `#[hermes(default = "path::to::fn")]`
+/// calls the given function instead. It is an error on tuple fields, which
+/// are read by position. The `IntoJs` derive accepts it on named fields, so
+/// both can be derived for one type, but ignores it.
+#[proc_macro_derive(FromJs, attributes(hermes))]
+pub fn derive_from_js(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    from_js::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `HostObject` for a struct with named fields, exposing each field
+/// as a read-only property converted with `IntoJs` only when a script reads
+/// it. Fields must implement `Clone`; the conversion works on a copy.
+///
+/// Property names follow `#[hermes(rename = "...")]` and
+/// `#[hermes(rename_all = "...")]` like the `IntoJs` derive.
+/// `#[hermes(default)]` is an error, as fields are never read from JS.
+#[proc_macro_derive(HostObject, attributes(hermes))]
+pub fn derive_host_object(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    host_object::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Turns a Rust function into an op that can be registered with
+/// `Runtime::register_op::<name>()`.
+///
+/// Arguments are converted with `FromJsArg`, missing ones passed as
+/// `undefined`, and the result with `IntoJsRet`. The function itself stays
+/// callable from Rust; the op is a type of the same name implementing
+/// `HermesOp`, whose JS name and `length` are the function's name and
+/// parameter count.
+///
+/// A leading parameter named `this` receives the call's receiver instead
+/// of an argument and does not count towards `length`.
+///
+/// A function taking a single `Args` parameter is variadic: it receives
+/// every argument unconverted and its `length` is 0. The type must be
+/// spelled `Args`, `rusty_hermes::Args` or `::rusty_hermes::Args`; other
+/// types named `Args` are converted like any parameter.
+#[proc_macro_attribute]
+pub fn hermes_op(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !attr.is_empty() {
+        return syn::Error::new_spanned(TokenStream2::from(attr), "`hermes_op` takes no arguments")
+            .into_compile_error()
+            .into();
+    }
+    let func = parse_macro_input!(item as ItemFn);
+    op::expand(func)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Adds the `'__rt` lifetime to `generics` and bounds every type parameter
+/// with `bound`.
+fn with_rt_lifetime(generics: &Generics, bound: TokenStream2) -> Generics {
+    let mut generics = generics.clone();
+    for param in &mut generics.params {
+        if let GenericParam::Type(ty) = param {
+            ty.bounds
+                .push(syn::parse2(bound.clone()).expect("valid bound"));
+        }
+    }
+    generics.params.insert(0, parse_quote!('__rt));
+    generics
+}
+
+/// Rejects internally tagged enums whose variants carry unnamed fields.
+fn check_tagged_variant(variant: &syn::Variant) -> syn::Result<()> {
+    match variant.fields {
+        syn::Fields::Unnamed(_) => Err(syn::Error::new_spanned(
+            variant,
+            "`tag` only supports unit and struct variants",
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Identifiers bound to the fields of a tuple variant or struct.
+fn field_bindings(count: usize) -> Vec<syn::Ident> {
+    (0..count)
+        .map(|i| quote::format_ident!("__field{}", i))
+        .collect()
+}
@@ -0,0 +1,133 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{FnArg, ItemFn, Pat, Type};
+
+pub(crate) fn expand(func: ItemFn) -> syn::Result<TokenStream> {
+    let sig = &func.sig;
+    if let Some(asyncness) = &sig.asyncness {
+        return Err(syn::Error::new_spanned(
+            asyncness,
+            "`hermes_op` functions cannot be async",
+        ));
+    }
+    if !sig
+        .generics
+        .params
+        .iter()
+        .all(|p| matches!(p, syn::GenericParam::Lifetime(_)))
+    {
+        return Err(syn::Error::new_spanned(
+            &sig.generics,
+            "`hermes_op` functions cannot have type or const parameters",
+        ));
+    }
+
+    let mut bindings = Vec::new();
+    let mut extracts = Vec::new();
+    let mut variadic = false;
+    let mut offset = 0;
+    for (index, arg) in sig.inputs.iter().enumerate() {
+        let FnArg::Typed(arg) = arg else {
+            return Err(syn::Error::new_spanned(
+                arg,
+                "`hermes_op` functions cannot take `self`",
+            ));
+        };
+        let Pat::Ident(pat) = &*arg.pat else {
+            return Err(syn::Error::new_spanned(
+                &arg.pat,
+                "`hermes_op` parameters must be plain identifiers",
+            ));
+        };
+        let ty = &arg.ty;
+        let binding = format_ident!("__arg{}", index);
+        if pat.ident == "this" {
+            if index != 0 {
+                return Err(syn::Error::new_spanned(
+                    &pat.ident,
+                    "`this` must be the first parameter",
+                ));
+            }
+            extracts.push(quote! {
+                let #binding = <#ty as ::rusty_hermes::FromJsArg<'__rt>>::from_arg(__this)?;
+            });
+            offset = 1;
+        } else if is_args(ty) {
+            if sig.inputs.len() != offset + 1 {
+                return Err(syn::Error::new_spanned(
+                    ty,
+                    "an `Args` parameter must be the only parameter besides `this`",
+                ));
+            }
+            extracts.push(quote! {
+                let #binding = ::rusty_hermes::Args::new(__rt, __args);
+            });
+            variadic = true;
+        } else {
+            let position = index - offset;
+            let param = pat.ident.to_string();
+            extracts.push(quote! {
+                let #binding = <#ty as ::rusty_hermes::FromJsArg<'__rt>>::from_arg(
+                    __args.get(#position).unwrap_or(&::rusty_hermes::Value::Undefined),
+                )
+                .map_err(|__err| ::rusty_hermes::__private::argument(__err, #position, #param))?;
+            });
+        }
+        bindings.push(binding);
+    }
+
+    let vis = &func.vis;
+    let ident = &sig.ident;
+    let name = ident.to_string();
+    let arity = if variadic {
+        0
+    } else {
+        (bindings.len() - offset) as u32
+    };
+    Ok(quote! {
+        #func
+
+        // A braced struct lives only in the type namespace, so it can share
+        // the function's name.
+        #[allow(non_camel_case_types)]
+        #[doc(hidden)]
+        #vis struct #ident {}
+
+        impl ::rusty_hermes::HermesOp for #ident {
+            const NAME: &'static str = #name;
+            const ARITY: u32 = #arity;
+
+            fn call<'__rt>(
+                __rt: &'__rt ::rusty_hermes::Runtime,
+                __this: &::rusty_hermes::Value<'__rt>,
+                __args: &[::rusty_hermes::Value<'__rt>],
+            ) -> ::rusty_hermes::Result<::rusty_hermes::Value<'__rt>> {
+                #(#extracts)*
+                ::rusty_hermes::IntoJsRet::into_ret(#ident(#(#bindings),*), __rt)
+            }
+        }
+    })
+}
+
+/// Whether `ty` names the variadic `Args` type, spelled `Args`,
+/// `rusty_hermes::Args` or `::rusty_hermes::Args`. Other paths ending in
+/// `Args`, like a user's own `my_mod::Args`, are converted as usual.
+fn is_args(ty: &Type) -> bool {
+    let Type::Path(path) = ty else {
+        return false;
+    };
+    if path.qself.is_some() {
+        return false;
+    }
+    let segments: Vec<String> = path
+        .path
+        .segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect();
+    match segments.as_slice() {
+        [name] => name == "Args" && path.path.leading_colon.is_none(),
+        [krate, name] => krate == "rusty_hermes" && name == "Args",
+        _ => false,
+    }
+}
@@ -0,0 +1,174 @@
+use libhermesabi_sys::*;
+
+use crate::{error, Error, FromJs, Object, Result, Runtime, Value};
+
+managed_pointer!(
+    /// A JavaScript array.
+    Array,
+    HermesABIArray,
+    clone_object(HermesABIObject)
+);
+
+impl<'rt> Array<'rt> {
+    /// Creates an array of `len` holes, like `new Array(len)`.
+    pub fn new(rt: &'rt Runtime, len: usize) -> Result<Self> {
+        let res = unsafe { abi_call!(rt, create_array, len) };
+        let ptr = error::check_pointer(rt, res.ptr_or_error)?;
+        Ok(unsafe { Array::from_raw(rt, ptr) })
+    }
+
+    /// The array's `length`.
+    pub fn len(&self) -> usize {
+        unsafe { abi_call!(self.rt, get_array_length, self.as_abi()) }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Reads the element at `index`, invoking getters.
+    pub fn get(&self, index: usize) -> Result<Value<'rt>> {
+        self.as_object().get(&index.to_string())
+    }
+
+    /// Writes the element at `index`, growing the array if needed.
+    pub fn set(&self, index: usize, value: Value<'rt>) -> Result<()> {
+        self.as_object().set(&index.to_string(), value)
+    }
+
+    /// Appends `value`, like `Array.prototype.push`.
+    pub fn push(&self, value: Value<'rt>) -> Result<()> {
+        let push = self.rt.builtin("Array.prototype.push")?;
+        push.call_with_this_internal(&self.clone().into(), &[value])?;
+        Ok(())
+    }
+
+    /// Removes and returns the last element, like `Array.prototype.pop`.
+    /// Returns `undefined` when the array is empty.
+    pub fn pop(&self) -> Result<Value<'rt>> {
+        let pop = self.rt.builtin("Array.prototype.pop")?;
+        pop.call_with_this_internal(&self.clone().into(), &[])
+    }
+
+    /// Reverses the elements in place, like `Array.prototype.reverse`.
+    pub fn reverse(&self) -> Result<()> {
+        let reverse = self.rt.builtin("Array.prototype.reverse")?;
+        reverse.call_with_this_internal(&self.clone().into(), &[])?;
+        Ok(())
+    }
+
+    /// A new array of the elements from `start` up to but excluding `end`,
+    /// like `Array.prototype.slice`. Both bounds are clamped to the length,
+    /// and `start >= end` gives an empty array.
+    pub fn slice(&self, start: usize, end: usize) -> Result<Array<'rt>> {
+        let slice = self.rt.builtin("Array.prototype.slice")?;
+        slice
+            .call_with_this_internal(
+                &self.clone().into(),
+                &[Value::Number(start as f64), Value::Number(end as f64)],
+            )?
+            .into_array()
+    }
+
+    /// A new array of this array's elements followed by `other`'s, like
+    /// `Array.prototype.concat`.
+    pub fn concat(&self, other: &Array<'rt>) -> Result<Array<'rt>> {
+        let concat = self.rt.builtin("Array.prototype.concat")?;
+        concat
+            .call_with_this_internal(&self.clone().into(), &[other.clone().into()])?
+            .into_array()
+    }
+
+    /// Index of the first element strictly equal to `needle`, using
+    /// `Array.prototype.indexOf`. `NaN` is never found.
+    pub fn index_of(&self, needle: &Value<'rt>) -> Result<Option<usize>> {
+        let index_of = self.rt.builtin("Array.prototype.indexOf")?;
+        let found =
+            index_of.call_with_this_internal(&self.clone().into(), std::slice::from_ref(needle))?;
+        match found.as_number() {
+            Some(i) if i >= 0.0 => Ok(Some(i as usize)),
+            Some(_) => Ok(None),
+            None => Err(Error::expected("number", &found)),
+        }
+    }
+
+    /// Whether an element equals `needle` under SameValueZero, using
+    /// `Array.prototype.includes`. Unlike [`index_of`](Self::index_of),
+    /// this finds `NaN`.
+    pub fn includes(&self, needle: &Value<'rt>) -> Result<bool> {
+        let includes = self.rt.builtin("Array.prototype.includes")?;
+        let found =
+            includes.call_with_this_internal(&self.clone().into(), std::slice::from_ref(needle))?;
+        found
+            .as_bool()
+            .ok_or_else(|| Error::expected("boolean", &found))
+    }
+
+    /// The array viewed as a plain object.
+    pub fn as_object(&self) -> Object<'rt> {
+        self.clone().into()
+    }
+
+    /// Iterates over the elements in index order.
+    ///
+    /// The length is read once when the iterator is created: elements
+    /// appended while iterating are not visited, and indices past a
+    /// shrunken end read as `undefined`.
+    pub fn iter(&self) -> ArrayIter<'rt> {
+        ArrayIter {
+            array: self.clone(),
+            index: 0,
+            len: self.len(),
+        }
+    }
+
+    /// Converts every element, stopping at the first failure.
+    pub fn to_vec<T: FromJs<'rt>>(&self) -> Result<Vec<T>> {
+        self.iter()
+            .enumerate()
+            .map(|(i, v)| T::from_js(&v?).map_err(|err| err.at(&format!("[{}]", i))))
+            .collect()
+    }
+}
+
+impl<'rt> From<Array<'rt>> for Object<'rt> {
+    fn from(a: Array<'rt>) -> Self {
+        let rt = a.rt;
+        unsafe { Object::from_raw(rt, a.into_raw()) }
+    }
+}
+
+impl<'rt> From<Array<'rt>> for Value<'rt> {
+    fn from(a: Array<'rt>) -> Self {
+        Value::Object(a.into())
+    }
+}
+
+/// Iterator over the elements of an [`Array`], created by [`Array::iter`].
+///
+/// Each step reads one element and yields the error if its getter throws.
+pub struct ArrayIter<'rt> {
+    array: Array<'rt>,
+    index: usize,
+    len: usize,
+}
+
+impl<'rt> Iterator for ArrayIter<'rt> {
+    type Item = Result<Value<'rt>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.len {
+            return None;
+        }
+        let item = self.array.get(self.index);
+        self.index += 1;
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.len - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for ArrayIter<'_> {}
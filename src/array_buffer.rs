@@ -0,0 +1,154 @@
+use libhermesabi_sys::*;
+
+use crate::buffer::OwnedMutableBuffer;
+use crate::{error, Error, Object, Result, Runtime, Value};
+
+managed_pointer!(
+    /// A JavaScript `ArrayBuffer`.
+    ///
+    /// The engine's storage is shared by every handle to the buffer and every
+    /// JS view of it, so the safe accessors copy bytes in and out. The slice
+    /// accessors [`data`](Self::data) and [`data_mut`](Self::data_mut)
+    /// borrow the storage directly and are `unsafe`.
+    ArrayBuffer,
+    HermesABIArrayBuffer,
+    clone_object(HermesABIObject)
+);
+
+impl<'rt> ArrayBuffer<'rt> {
+    /// Creates a zero-filled buffer of `len` bytes, backed by Rust memory
+    /// that the engine frees when it collects the buffer.
+    pub fn new(rt: &'rt Runtime, len: usize) -> Result<Self> {
+        Self::from_vec(rt, vec![0; len])
+    }
+
+    /// Creates a buffer holding a copy of `bytes`.
+    pub fn from_slice(rt: &'rt Runtime, bytes: &[u8]) -> Result<Self> {
+        Self::from_vec(rt, bytes.to_vec())
+    }
+
+    fn from_vec(rt: &'rt Runtime, bytes: Vec<u8>) -> Result<Self> {
+        let storage = OwnedMutableBuffer::into_abi(bytes);
+        // The runtime owns `storage` from here on and releases it even on
+        // failure.
+        let res = unsafe { abi_call!(rt, create_arraybuffer_from_external_data, storage) };
+        let ptr = error::check_pointer(rt, res.ptr_or_error)?;
+        Ok(unsafe { ArrayBuffer::from_raw(rt, ptr) })
+    }
+
+    /// Size in bytes, the JS `byteLength`.
+    pub fn len(&self) -> Result<usize> {
+        error::check_size(self.rt, unsafe {
+            abi_call!(self.rt, get_arraybuffer_size, self.as_abi())
+        })
+    }
+
+    pub fn is_empty(&self) -> Result<bool> {
+        Ok(self.len()? == 0)
+    }
+
+    /// The bytes, borrowed from the engine's storage.
+    ///
+    /// # Safety
+    ///
+    /// While the slice is alive, the bytes must not be written through any
+    /// other handle, view or slice, and no JS may run: JS can write to the
+    /// buffer or detach it. Prefer [`to_vec`](Self::to_vec) or
+    /// [`copy_to_slice`](Self::copy_to_slice).
+    pub unsafe fn data(&self) -> Result<&[u8]> {
+        let len = self.len()?;
+        if len == 0 {
+            return Ok(&[]);
+        }
+        Ok(std::slice::from_raw_parts(self.data_ptr()?, len))
+    }
+
+    /// The bytes, mutably borrowed from the engine's storage.
+    ///
+    /// # Safety
+    ///
+    /// While the slice is alive, the bytes must not be read or written
+    /// through any other handle, view or slice, and no JS may run. Prefer
+    /// [`copy_from_slice`](Self::copy_from_slice).
+    pub unsafe fn data_mut(&mut self) -> Result<&mut [u8]> {
+        let len = self.len()?;
+        if len == 0 {
+            return Ok(&mut []);
+        }
+        Ok(std::slice::from_raw_parts_mut(self.data_ptr()?, len))
+    }
+
+    /// Overwrites the contents with `bytes`, which must have the buffer's
+    /// length.
+    pub fn copy_from_slice(&mut self, bytes: &[u8]) -> Result<()> {
+        let len = self.len()?;
+        if len != bytes.len() {
+            return Err(Error::TypeError(format!(
+                "expected {} bytes, got {}",
+                len,
+                bytes.len()
+            )));
+        }
+        if len > 0 {
+            // No Rust reference to the storage is formed, and no JS runs
+            // during the copy.
+            unsafe { std::ptr::copy_nonoverlapping(bytes.as_ptr(), self.data_ptr()?, len) };
+        }
+        Ok(())
+    }
+
+    /// Copies the contents out.
+    pub fn to_vec(&self) -> Result<Vec<u8>> {
+        let mut bytes = vec![0; self.len()?];
+        self.copy_to_slice(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    /// Copies as many bytes as fit into `dst`, without allocating, and
+    /// returns how many were copied. A result shorter than [`len`] means
+    /// `dst` was too small for the whole buffer.
+    ///
+    /// [`len`]: ArrayBuffer::len
+    pub fn copy_to_slice(&self, dst: &mut [u8]) -> Result<usize> {
+        let n = self.len()?.min(dst.len());
+        if n > 0 {
+            // `dst` is Rust memory, so it cannot overlap the storage.
+            unsafe { std::ptr::copy_nonoverlapping(self.data_ptr()?, dst.as_mut_ptr(), n) };
+        }
+        Ok(n)
+    }
+
+    /// Copies the whole buffer into `dst`, which must have the buffer's
+    /// length. A mismatch is an [`Error::RuntimeError`] and copies nothing.
+    pub fn copy_to_slice_exact(&self, dst: &mut [u8]) -> Result<()> {
+        let len = self.len()?;
+        if len != dst.len() {
+            return Err(Error::RuntimeError(format!(
+                "expected a {}-byte slice, got {}",
+                len,
+                dst.len()
+            )));
+        }
+        self.copy_to_slice(dst)?;
+        Ok(())
+    }
+
+    pub(crate) fn data_ptr(&self) -> Result<*mut u8> {
+        error::check_u8_ptr(self.rt, unsafe {
+            abi_call!(self.rt, get_arraybuffer_data, self.as_abi())
+        })
+    }
+}
+
+impl<'rt> From<ArrayBuffer<'rt>> for Object<'rt> {
+    fn from(buffer: ArrayBuffer<'rt>) -> Self {
+        let rt = buffer.rt;
+        unsafe { Object::from_raw(rt, buffer.into_raw()) }
+    }
+}
+
+impl<'rt> From<ArrayBuffer<'rt>> for Value<'rt> {
+    fn from(buffer: ArrayBuffer<'rt>) -> Self {
+        Value::Object(buffer.into())
+    }
+}
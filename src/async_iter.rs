@@ -0,0 +1,48 @@
+use std::cell::RefCell;
+
+use crate::{Error, Function, IntoJs, Object, Promise, PropNameId, Result, Runtime, Value};
+
+/// Builds the object behind [`Runtime::create_async_iterable`].
+pub(crate) fn create_async_iterable<'rt, I>(rt: &'rt Runtime, stream: I) -> Result<Object<'rt>>
+where
+    I: IntoIterator,
+    I::IntoIter: 'static,
+    I::Item: for<'a> IntoJs<'a>,
+{
+    let stream = RefCell::new(stream.into_iter());
+    let next = Function::from_host_fn(rt, "next", 0, move |rt, _this, _args| {
+        // Release the borrow before converting, which may run JS.
+        let item = stream.borrow_mut().next();
+        let result = Object::new(rt)?;
+        let done = match item {
+            Some(item) => {
+                result.set("value", item.into_js(rt)?)?;
+                false
+            }
+            None => true,
+        };
+        result.set("done", Value::Bool(done))?;
+        Ok(Promise::resolve(rt, result.into())?.into())
+    })?;
+    let iterator = Function::from_host_fn(rt, "[Symbol.asyncIterator]", 0, |_rt, this, _args| {
+        Ok(this.clone())
+    })?;
+
+    let async_iterator = match rt
+        .global()
+        .get("Symbol")?
+        .into_object()?
+        .get("asyncIterator")?
+    {
+        Value::Symbol(symbol) => symbol,
+        _ => {
+            return Err(Error::RuntimeError(
+                "Symbol.asyncIterator is not supported by this runtime".into(),
+            ))
+        }
+    };
+    let iterable = Object::new(rt)?;
+    iterable.set("next", next.into())?;
+    iterable.set_with_propnameid(&PropNameId::from_symbol(&async_iterator)?, iterator.into())?;
+    Ok(iterable)
+}
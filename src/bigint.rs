@@ -0,0 +1,64 @@
+use libhermesabi_sys::*;
+
+use crate::{error, Error, JsString, Result, Runtime};
+
+managed_pointer!(
+    /// A JavaScript BigInt.
+    BigInt,
+    HermesABIBigInt,
+    clone_bigint(HermesABIBigInt)
+);
+
+impl<'rt> BigInt<'rt> {
+    pub fn from_i64(rt: &'rt Runtime, value: i64) -> Result<Self> {
+        let res = unsafe { abi_call!(rt, create_bigint_from_int64, value) };
+        let ptr = error::check_pointer(rt, res.ptr_or_error)?;
+        Ok(unsafe { BigInt::from_raw(rt, ptr) })
+    }
+
+    pub fn from_u64(rt: &'rt Runtime, value: u64) -> Result<Self> {
+        let res = unsafe { abi_call!(rt, create_bigint_from_uint64, value) };
+        let ptr = error::check_pointer(rt, res.ptr_or_error)?;
+        Ok(unsafe { BigInt::from_raw(rt, ptr) })
+    }
+
+    /// Whether the value fits in an `i64` without loss.
+    pub fn is_i64(&self) -> bool {
+        unsafe { abi_call!(self.rt, bigint_is_int64, self.as_abi()) }
+    }
+
+    /// Whether the value fits in a `u64` without loss.
+    pub fn is_u64(&self) -> bool {
+        unsafe { abi_call!(self.rt, bigint_is_uint64, self.as_abi()) }
+    }
+
+    /// The low 64 bits of the value.
+    pub fn truncate_to_u64(&self) -> u64 {
+        unsafe { abi_call!(self.rt, bigint_truncate_to_uint64, self.as_abi()) }
+    }
+
+    /// The low 64 bits of the value, as two's complement.
+    pub fn truncate_to_i64(&self) -> i64 {
+        self.truncate_to_u64() as i64
+    }
+
+    /// The nearest `f64`, like JS `Number(bigint)`. Values beyond 2^53 in
+    /// magnitude lose precision, so this suits display and metrics rather
+    /// than arithmetic. If a script has replaced the global `Number` with
+    /// something that does not return a number, this is an
+    /// [`Error::TypeError`](crate::Error::TypeError).
+    pub fn to_f64_lossy(&self) -> Result<f64> {
+        let number = self.rt.builtin("Number")?;
+        let value = number.call_internal(&[self.clone().into()])?;
+        value
+            .as_number()
+            .ok_or_else(|| Error::expected("Number() to return a number", &value))
+    }
+
+    /// Formats the value in the given radix (2 to 36).
+    pub fn to_js_string(&self, radix: u32) -> Result<JsString<'rt>> {
+        let res = unsafe { abi_call!(self.rt, bigint_to_string, self.as_abi(), radix) };
+        let ptr = error::check_pointer(self.rt, res.ptr_or_error)?;
+        Ok(unsafe { JsString::from_raw(self.rt, ptr) })
+    }
+}
@@ -0,0 +1,118 @@
+//! Rust-owned implementations of the ABI buffer types.
+
+use libhermesabi_sys::*;
+
+/// An immutable buffer handed to the engine, which releases it when done.
+#[repr(C)]
+pub(crate) struct OwnedBuffer {
+    base: HermesABIBuffer,
+    data: Vec<u8>,
+}
+
+static OWNED_BUFFER_VTABLE: HermesABIBufferVTable = HermesABIBufferVTable {
+    release: Some(release_owned_buffer),
+};
+
+unsafe extern "C" fn release_owned_buffer(buf: *mut HermesABIBuffer) {
+    drop(Box::from_raw(buf as *mut OwnedBuffer));
+}
+
+impl OwnedBuffer {
+    /// Moves `data` to the heap. Ownership passes to whoever receives the
+    /// returned pointer; it is freed through the buffer's `release` entry.
+    pub(crate) fn into_abi(data: Vec<u8>) -> *mut HermesABIBuffer {
+        let mut buf = Box::new(OwnedBuffer {
+            base: HermesABIBuffer {
+                vtable: &OWNED_BUFFER_VTABLE,
+                data: std::ptr::null(),
+                size: data.len(),
+            },
+            data,
+        });
+        buf.base.data = buf.data.as_ptr();
+        Box::into_raw(buf) as *mut HermesABIBuffer
+    }
+}
+
+/// A mutable buffer handed to the engine as `ArrayBuffer` storage, released
+/// when the engine collects the buffer.
+#[repr(C)]
+pub(crate) struct OwnedMutableBuffer {
+    base: HermesABIMutableBuffer,
+    data: Vec<u8>,
+}
+
+static OWNED_MUTABLE_BUFFER_VTABLE: HermesABIMutableBufferVTable = HermesABIMutableBufferVTable {
+    release: Some(release_owned_mutable_buffer),
+};
+
+unsafe extern "C" fn release_owned_mutable_buffer(buf: *mut HermesABIMutableBuffer) {
+    drop(Box::from_raw(buf as *mut OwnedMutableBuffer));
+}
+
+impl OwnedMutableBuffer {
+    /// Moves `data` to the heap, like [`OwnedBuffer::into_abi`].
+    pub(crate) fn into_abi(mut data: Vec<u8>) -> *mut HermesABIMutableBuffer {
+        let buf = Box::new(OwnedMutableBuffer {
+            base: HermesABIMutableBuffer {
+                vtable: &OWNED_MUTABLE_BUFFER_VTABLE,
+                data: data.as_mut_ptr(),
+                size: data.len(),
+            },
+            data,
+        });
+        Box::into_raw(buf) as *mut HermesABIMutableBuffer
+    }
+}
+
+/// A buffer the engine grows on demand while writing output into it.
+#[repr(C)]
+pub(crate) struct GrowableBuffer {
+    base: HermesABIGrowableBuffer,
+    storage: Vec<u8>,
+}
+
+static GROWABLE_BUFFER_VTABLE: HermesABIGrowableBufferVTable = HermesABIGrowableBufferVTable {
+    try_grow_to: Some(grow_buffer),
+};
+
+unsafe extern "C" fn grow_buffer(buf: *mut HermesABIGrowableBuffer, size: usize) {
+    let this = &mut *(buf as *mut GrowableBuffer);
+    if size <= this.storage.len() {
+        return;
+    }
+    this.storage.resize(size, 0);
+    this.base.data = this.storage.as_mut_ptr();
+    this.base.size = size;
+}
+
+impl GrowableBuffer {
+    const INITIAL_SIZE: usize = 64;
+
+    pub(crate) fn new() -> Self {
+        let mut storage = vec![0; Self::INITIAL_SIZE];
+        GrowableBuffer {
+            base: HermesABIGrowableBuffer {
+                vtable: &GROWABLE_BUFFER_VTABLE,
+                data: storage.as_mut_ptr(),
+                size: storage.len(),
+                used: 0,
+            },
+            storage,
+        }
+    }
+
+    /// Pointer passed to the engine. `self` must not move while it is in use.
+    pub(crate) fn as_abi(&mut self) -> *mut HermesABIGrowableBuffer {
+        &mut self.base
+    }
+
+    pub(crate) fn into_bytes(mut self) -> Vec<u8> {
+        self.storage.truncate(self.base.used);
+        self.storage
+    }
+
+    pub(crate) fn into_string_lossy(self) -> String {
+        String::from_utf8_lossy(&self.into_bytes()).into_owned()
+    }
+}
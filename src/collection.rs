@@ -0,0 +1,183 @@
+use libhermesabi_sys::*;
+
+use crate::{FromJs, IntoJs, Object, Result, Runtime, Value};
+
+managed_pointer!(
+    /// A JavaScript `Map`, whose keys can be any value.
+    JsMap,
+    HermesABIObject,
+    clone_object(HermesABIObject)
+);
+
+managed_pointer!(
+    /// A JavaScript `Set`.
+    JsSet,
+    HermesABIObject,
+    clone_object(HermesABIObject)
+);
+
+/// Calls the built-in method at `path` with `this` set to `object`.
+fn call_builtin<'rt>(
+    object: &Object<'rt>,
+    path: &'static str,
+    args: &[Value<'rt>],
+) -> Result<Value<'rt>> {
+    let method = object.rt.builtin(path)?;
+    method.call_with_this_internal(&object.clone().into(), args)
+}
+
+/// Calls the `size` getter of the built-in `constructor`'s prototype, so
+/// neither an own `size` property nor a reassigned global can change it.
+/// `key` caches the getter.
+fn size(object: &Object, constructor: &'static str, key: &'static str) -> Result<usize> {
+    let rt = object.rt;
+    let getter = rt.cached_function(key, || {
+        let prototype = Object::from(rt.builtin(constructor)?).get("prototype")?;
+        let descriptor = rt
+            .builtin("Object.getOwnPropertyDescriptor")?
+            .call_internal(&[prototype, "size".into_js(rt)?])?
+            .into_object()?;
+        descriptor.get("get")?.into_function()
+    })?;
+    usize::from_js(&getter.call_with_this_internal(&object.clone().into(), &[])?)
+}
+
+/// Returns `Ok(None)` for values that are not instances of the global
+/// `constructor`.
+fn instance<'rt>(value: &Value<'rt>, constructor: &'static str) -> Result<Option<Object<'rt>>> {
+    let Value::Object(object) = value else {
+        return Ok(None);
+    };
+    let constructor = object.rt.builtin(constructor)?;
+    Ok(object.instance_of(&constructor)?.then(|| object.clone()))
+}
+
+impl<'rt> JsMap<'rt> {
+    /// Creates an empty map, like `new Map()`.
+    pub fn new(rt: &'rt Runtime) -> Result<Self> {
+        let map = rt.builtin("Map")?.call_as_constructor_internal(&[])?;
+        Ok(Self::from_value(&map)?.expect("`new Map()` returns a map"))
+    }
+
+    pub(crate) fn from_value(value: &Value<'rt>) -> Result<Option<Self>> {
+        Ok(instance(value, "Map")?.map(|object| {
+            let rt = object.rt;
+            unsafe { JsMap::from_raw(rt, object.into_raw()) }
+        }))
+    }
+
+    /// The value stored under `key`, or `undefined`.
+    pub fn get(&self, key: impl IntoJs<'rt>) -> Result<Value<'rt>> {
+        call_builtin(
+            &self.as_object(),
+            "Map.prototype.get",
+            &[key.into_js(self.rt)?],
+        )
+    }
+
+    pub fn set(&self, key: impl IntoJs<'rt>, value: impl IntoJs<'rt>) -> Result<()> {
+        let args = [key.into_js(self.rt)?, value.into_js(self.rt)?];
+        call_builtin(&self.as_object(), "Map.prototype.set", &args)?;
+        Ok(())
+    }
+
+    pub fn has(&self, key: impl IntoJs<'rt>) -> Result<bool> {
+        let has = call_builtin(
+            &self.as_object(),
+            "Map.prototype.has",
+            &[key.into_js(self.rt)?],
+        )?;
+        Ok(has.as_bool() == Some(true))
+    }
+
+    /// Removes `key`, returning whether it was present.
+    pub fn delete(&self, key: impl IntoJs<'rt>) -> Result<bool> {
+        let args = [key.into_js(self.rt)?];
+        let deleted = call_builtin(&self.as_object(), "Map.prototype.delete", &args)?;
+        Ok(deleted.as_bool() == Some(true))
+    }
+
+    /// The number of entries.
+    pub fn size(&self) -> Result<usize> {
+        size(&self.as_object(), "Map", "<Map size>")
+    }
+
+    pub fn as_object(&self) -> Object<'rt> {
+        self.clone().into()
+    }
+}
+
+impl<'rt> JsSet<'rt> {
+    /// Creates an empty set, like `new Set()`.
+    pub fn new(rt: &'rt Runtime) -> Result<Self> {
+        let set = rt.builtin("Set")?.call_as_constructor_internal(&[])?;
+        Ok(Self::from_value(&set)?.expect("`new Set()` returns a set"))
+    }
+
+    pub(crate) fn from_value(value: &Value<'rt>) -> Result<Option<Self>> {
+        Ok(instance(value, "Set")?.map(|object| {
+            let rt = object.rt;
+            unsafe { JsSet::from_raw(rt, object.into_raw()) }
+        }))
+    }
+
+    pub fn add(&self, value: impl IntoJs<'rt>) -> Result<()> {
+        call_builtin(
+            &self.as_object(),
+            "Set.prototype.add",
+            &[value.into_js(self.rt)?],
+        )?;
+        Ok(())
+    }
+
+    pub fn has(&self, value: impl IntoJs<'rt>) -> Result<bool> {
+        let has = call_builtin(
+            &self.as_object(),
+            "Set.prototype.has",
+            &[value.into_js(self.rt)?],
+        )?;
+        Ok(has.as_bool() == Some(true))
+    }
+
+    /// Removes `value`, returning whether it was present.
+    pub fn delete(&self, value: impl IntoJs<'rt>) -> Result<bool> {
+        let args = [value.into_js(self.rt)?];
+        let deleted = call_builtin(&self.as_object(), "Set.prototype.delete", &args)?;
+        Ok(deleted.as_bool() == Some(true))
+    }
+
+    /// The number of values.
+    pub fn size(&self) -> Result<usize> {
+        size(&self.as_object(), "Set", "<Set size>")
+    }
+
+    pub fn as_object(&self) -> Object<'rt> {
+        self.clone().into()
+    }
+}
+
+impl<'rt> From<JsMap<'rt>> for Object<'rt> {
+    fn from(map: JsMap<'rt>) -> Self {
+        let rt = map.rt;
+        unsafe { Object::from_raw(rt, map.into_raw()) }
+    }
+}
+
+impl<'rt> From<JsMap<'rt>> for Value<'rt> {
+    fn from(map: JsMap<'rt>) -> Self {
+        Value::Object(map.into())
+    }
+}
+
+impl<'rt> From<JsSet<'rt>> for Object<'rt> {
+    fn from(set: JsSet<'rt>) -> Self {
+        let rt = set.rt;
+        unsafe { Object::from_raw(rt, set.into_raw()) }
+    }
+}
+
+impl<'rt> From<JsSet<'rt>> for Value<'rt> {
+    fn from(set: JsSet<'rt>) -> Self {
+        Value::Object(set.into())
+    }
+}
@@ -0,0 +1,84 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::{Function, Object, Result, Runtime, Value};
+
+/// The `console` method a message was logged through, passed to the sink
+/// of [`Runtime::install_console_with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConsoleLevel {
+    Log,
+    Info,
+    Warn,
+    Error,
+}
+
+impl ConsoleLevel {
+    const ALL: [ConsoleLevel; 4] = [
+        ConsoleLevel::Log,
+        ConsoleLevel::Info,
+        ConsoleLevel::Warn,
+        ConsoleLevel::Error,
+    ];
+
+    /// The method name, e.g. `"warn"`.
+    pub fn name(self) -> &'static str {
+        match self {
+            ConsoleLevel::Log => "log",
+            ConsoleLevel::Info => "info",
+            ConsoleLevel::Warn => "warn",
+            ConsoleLevel::Error => "error",
+        }
+    }
+}
+
+/// Formats one argument the way browsers print it: strings and other
+/// primitives as `String(x)`, errors as `Name: message`, and other objects
+/// as JSON, falling back to `String(x)` when JSON cannot represent them.
+fn format_arg(rt: &Runtime, value: &Value) -> String {
+    if let Value::Object(object) = value {
+        let is_error = rt
+            .builtin("Error")
+            .and_then(|error| object.instance_of(&error))
+            .unwrap_or(false);
+        if !is_error && !object.is_function() {
+            if let Ok(json) = value.to_json_string(None) {
+                return json;
+            }
+        }
+    }
+    value.to_string()
+}
+
+/// Defines `console`, whose methods share `sink`. The engine owns the
+/// methods, so `sink` lives until the last of them is collected, at the
+/// latest when the runtime is dropped.
+pub(crate) fn install(
+    rt: &Runtime,
+    sink: impl FnMut(ConsoleLevel, String) + 'static,
+) -> Result<()> {
+    let sink = Rc::new(RefCell::new(sink));
+    let console = Object::new(rt)?;
+    for level in ConsoleLevel::ALL {
+        let sink = sink.clone();
+        let method = Function::from_host_fn(rt, level.name(), 0, move |rt, _this, args| {
+            let line = args
+                .iter()
+                .map(|arg| format_arg(rt, arg))
+                .collect::<Vec<_>>()
+                .join(" ");
+            (sink.borrow_mut())(level, line);
+            Ok(Value::Undefined)
+        })?;
+        console.set(level.name(), method.into())?;
+    }
+    rt.global().set("console", console.into())
+}
+
+/// Writes `log` and `info` to stdout and `warn` and `error` to stderr.
+pub(crate) fn print(level: ConsoleLevel, line: String) {
+    match level {
+        ConsoleLevel::Log | ConsoleLevel::Info => println!("{}", line),
+        ConsoleLevel::Warn | ConsoleLevel::Error => eprintln!("{}", line),
+    }
+}
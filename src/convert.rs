@@ -0,0 +1,486 @@
+//! Conversions between Rust types and JS values.
+
+use std::borrow::Cow;
+use std::num::{NonZeroI32, NonZeroI64, NonZeroU32, NonZeroU64};
+use std::ops::{Range, RangeInclusive};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::{
+    Array, ArrayBuffer, BigInt, Error, Function, JsMap, JsSet, JsString, Object, Promise, Result,
+    Runtime, Symbol, TypedArray, TypedArrayElement, Value,
+};
+
+/// Converts a Rust value into a JS value owned by `rt`.
+pub trait IntoJs<'rt> {
+    fn into_js(self, rt: &'rt Runtime) -> Result<Value<'rt>>;
+}
+
+/// Converts a JS value into a Rust value.
+pub trait FromJs<'rt>: Sized {
+    fn from_js(value: &Value<'rt>) -> Result<Self>;
+}
+
+impl<'rt> IntoJs<'rt> for Value<'rt> {
+    fn into_js(self, _rt: &'rt Runtime) -> Result<Value<'rt>> {
+        Ok(self)
+    }
+}
+
+impl<'rt> IntoJs<'rt> for &Value<'rt> {
+    fn into_js(self, _rt: &'rt Runtime) -> Result<Value<'rt>> {
+        Ok(self.clone())
+    }
+}
+
+impl<'rt> FromJs<'rt> for Value<'rt> {
+    fn from_js(value: &Value<'rt>) -> Result<Self> {
+        Ok(value.clone())
+    }
+}
+
+impl<'rt> IntoJs<'rt> for () {
+    fn into_js(self, _rt: &'rt Runtime) -> Result<Value<'rt>> {
+        Ok(Value::Undefined)
+    }
+}
+
+impl<'rt> IntoJs<'rt> for bool {
+    fn into_js(self, _rt: &'rt Runtime) -> Result<Value<'rt>> {
+        Ok(Value::Bool(self))
+    }
+}
+
+impl<'rt> FromJs<'rt> for bool {
+    fn from_js(value: &Value<'rt>) -> Result<Self> {
+        value
+            .as_bool()
+            .ok_or_else(|| Error::expected("boolean", value))
+    }
+}
+
+/// Numeric types are carried as JS numbers. Reading casts from `f64` with
+/// Rust's saturating `as` semantics.
+macro_rules! impl_from_js_via_f64 {
+    ($($ty:ty),*) => {$(
+        impl<'rt> IntoJs<'rt> for $ty {
+            fn into_js(self, _rt: &'rt Runtime) -> Result<Value<'rt>> {
+                Ok(Value::Number(self as f64))
+            }
+        }
+
+        impl<'rt> FromJs<'rt> for $ty {
+            fn from_js(value: &Value<'rt>) -> Result<Self> {
+                value
+                    .as_number()
+                    .map(|n| n as $ty)
+                    .ok_or_else(|| Error::expected("number", value))
+            }
+        }
+    )*};
+}
+
+impl_from_js_via_f64!(f64, f32, i8, i16, i32, isize, u8, u16, u32, usize);
+
+/// Largest integer a JS number holds exactly, `Number.MAX_SAFE_INTEGER`.
+pub(crate) const MAX_SAFE_INTEGER: f64 = 9007199254740991.0;
+
+/// 64-bit integers convert without loss: values a JS number holds exactly
+/// become numbers, larger ones BigInts. Reading accepts a BigInt that fits,
+/// or an integral number within the safe range.
+macro_rules! impl_from_js_exact_64 {
+    ($($ty:ty => $from:ident, $fits:ident, $truncate:ident);*) => {$(
+        impl<'rt> IntoJs<'rt> for $ty {
+            fn into_js(self, rt: &'rt Runtime) -> Result<Value<'rt>> {
+                if (self as f64).abs() <= MAX_SAFE_INTEGER {
+                    Ok(Value::Number(self as f64))
+                } else {
+                    Ok(Value::BigInt(BigInt::$from(rt, self)?))
+                }
+            }
+        }
+
+        impl<'rt> FromJs<'rt> for $ty {
+            fn from_js(value: &Value<'rt>) -> Result<Self> {
+                match value {
+                    Value::BigInt(big) if big.$fits() => Ok(big.$truncate()),
+                    Value::BigInt(_) => Err(Error::TypeError(format!(
+                        "BigInt out of range for {}",
+                        stringify!($ty)
+                    ))),
+                    Value::Number(n)
+                        if n.fract() == 0.0
+                            && n.abs() <= MAX_SAFE_INTEGER
+                            && *n >= <$ty>::MIN as f64 =>
+                    {
+                        Ok(*n as $ty)
+                    }
+                    Value::Number(n) => Err(Error::TypeError(format!(
+                        "expected a safe integer for {}, got {}",
+                        stringify!($ty),
+                        n
+                    ))),
+                    other => Err(Error::expected("number or bigint", other)),
+                }
+            }
+        }
+    )*};
+}
+
+impl_from_js_exact_64!(
+    i64 => from_i64, is_i64, truncate_to_i64;
+    u64 => from_u64, is_u64, truncate_to_u64
+);
+
+/// Carried like the underlying integer. Reading rejects zero with
+/// [`Error::RuntimeError`] and fractional or out-of-range numbers with
+/// [`Error::TypeError`].
+macro_rules! impl_non_zero {
+    ($($ty:ty => $int:ty),*) => {$(
+        impl<'rt> IntoJs<'rt> for $ty {
+            fn into_js(self, rt: &'rt Runtime) -> Result<Value<'rt>> {
+                self.get().into_js(rt)
+            }
+        }
+
+        impl<'rt> FromJs<'rt> for $ty {
+            fn from_js(value: &Value<'rt>) -> Result<Self> {
+                if let Value::Number(n) = value {
+                    if n.fract() != 0.0 || *n < <$int>::MIN as f64 || *n > <$int>::MAX as f64 {
+                        return Err(Error::TypeError(format!(
+                            "expected an integer for {}, got {}",
+                            stringify!($ty),
+                            n
+                        )));
+                    }
+                }
+                <$ty>::new(<$int>::from_js(value)?)
+                    .ok_or_else(|| Error::RuntimeError("value must be non-zero".into()))
+            }
+        }
+    )*};
+}
+
+impl_non_zero!(
+    NonZeroI32 => i32,
+    NonZeroU32 => u32,
+    NonZeroI64 => i64,
+    NonZeroU64 => u64
+);
+
+impl<'rt> IntoJs<'rt> for char {
+    fn into_js(self, rt: &'rt Runtime) -> Result<Value<'rt>> {
+        self.encode_utf8(&mut [0; 4]).into_js(rt)
+    }
+}
+
+/// Reads a string holding exactly one Unicode scalar value. A lone surrogate
+/// has no `char` equivalent and is reported as [`Error::RuntimeError`].
+impl<'rt> FromJs<'rt> for char {
+    fn from_js(value: &Value<'rt>) -> Result<Self> {
+        let Value::String(js) = value else {
+            return Err(Error::expected("string", value));
+        };
+        let s = js.to_rust_string()?;
+        let mut chars = s.chars();
+        let (Some(c), None) = (chars.next(), chars.next()) else {
+            return Err(Error::TypeError(format!(
+                "expected a single character, got {} characters",
+                s.chars().count()
+            )));
+        };
+        // The engine converts lone surrogates to U+FFFD, so check the
+        // original code unit before accepting a replacement character.
+        if c == char::REPLACEMENT_CHARACTER {
+            let char_code_at = js.rt.builtin("String.prototype.charCodeAt")?;
+            let unit = char_code_at.call_with_this_internal(value, &[Value::Number(0.0)])?;
+            if matches!(unit.as_number(), Some(n) if (0xD800..=0xDFFF).contains(&(n as u32))) {
+                return Err(Error::RuntimeError(
+                    "string is a lone UTF-16 surrogate".into(),
+                ));
+            }
+        }
+        Ok(c)
+    }
+}
+
+impl<'rt> IntoJs<'rt> for &str {
+    fn into_js(self, rt: &'rt Runtime) -> Result<Value<'rt>> {
+        Ok(Value::String(JsString::new(rt, self)?))
+    }
+}
+
+impl<'rt> IntoJs<'rt> for String {
+    fn into_js(self, rt: &'rt Runtime) -> Result<Value<'rt>> {
+        self.as_str().into_js(rt)
+    }
+}
+
+impl<'rt> FromJs<'rt> for String {
+    fn from_js(value: &Value<'rt>) -> Result<Self> {
+        match value {
+            Value::String(s) => s.to_rust_string(),
+            other => Err(Error::expected("string", other)),
+        }
+    }
+}
+
+impl<'rt> IntoJs<'rt> for Cow<'_, str> {
+    fn into_js(self, rt: &'rt Runtime) -> Result<Value<'rt>> {
+        self.as_ref().into_js(rt)
+    }
+}
+
+/// Always the owned variant, as the text is copied out of the engine.
+impl<'rt> FromJs<'rt> for Cow<'static, str> {
+    fn from_js(value: &Value<'rt>) -> Result<Self> {
+        String::from_js(value).map(Cow::Owned)
+    }
+}
+
+/// A number of milliseconds, the unit of JS timers and `Date` arithmetic.
+/// Sub-millisecond parts survive as fractions.
+impl<'rt> IntoJs<'rt> for Duration {
+    fn into_js(self, _rt: &'rt Runtime) -> Result<Value<'rt>> {
+        Ok(Value::Number(self.as_secs_f64() * 1000.0))
+    }
+}
+
+/// Reads a non-negative, finite number of milliseconds.
+impl<'rt> FromJs<'rt> for Duration {
+    fn from_js(value: &Value<'rt>) -> Result<Self> {
+        let ms = f64::from_js(value)?;
+        Duration::try_from_secs_f64(ms / 1000.0).map_err(|_| {
+            Error::TypeError(format!(
+                "expected a non-negative duration in milliseconds, got {}",
+                ms
+            ))
+        })
+    }
+}
+
+/// A JS `Date`, which keeps whole milliseconds since the epoch; finer
+/// precision is truncated.
+impl<'rt> IntoJs<'rt> for SystemTime {
+    fn into_js(self, rt: &'rt Runtime) -> Result<Value<'rt>> {
+        let ms = match self.duration_since(UNIX_EPOCH) {
+            Ok(after) => after.as_secs_f64() * 1000.0,
+            Err(before) => -(before.duration().as_secs_f64() * 1000.0),
+        };
+        rt.builtin("Date")?
+            .call_as_constructor_internal(&[Value::Number(ms)])
+    }
+}
+
+/// Reads a `Date` object or a number of milliseconds since the epoch.
+impl<'rt> FromJs<'rt> for SystemTime {
+    fn from_js(value: &Value<'rt>) -> Result<Self> {
+        let ms = match value {
+            Value::Number(ms) => *ms,
+            Value::Object(date) => {
+                let get_time = date.rt.builtin("Date.prototype.getTime")?;
+                f64::from_js(&get_time.call_with_this_internal(value, &[])?)?
+            }
+            other => return Err(Error::expected("Date or number", other)),
+        };
+        let invalid = || Error::TypeError(format!("expected a valid date, got {}", ms));
+        let offset = Duration::try_from_secs_f64(ms.abs() / 1000.0).map_err(|_| invalid())?;
+        let time = if ms >= 0.0 {
+            UNIX_EPOCH.checked_add(offset)
+        } else {
+            UNIX_EPOCH.checked_sub(offset)
+        };
+        time.ok_or_else(invalid)
+    }
+}
+
+/// `{ start, end }`, with `end` excluded.
+impl<'rt, T: IntoJs<'rt>> IntoJs<'rt> for Range<T> {
+    fn into_js(self, rt: &'rt Runtime) -> Result<Value<'rt>> {
+        let obj = Object::new(rt)?;
+        obj.set("start", self.start.into_js(rt)?)?;
+        obj.set("end", self.end.into_js(rt)?)?;
+        Ok(obj.into())
+    }
+}
+
+/// Reads `{ start, end }`. An object marked `inclusive: true` is rejected.
+impl<'rt, T: FromJs<'rt>> FromJs<'rt> for Range<T> {
+    fn from_js(value: &Value<'rt>) -> Result<Self> {
+        let (start, end, inclusive) = range_bounds(value)?;
+        if inclusive {
+            return Err(Error::TypeError(
+                "expected an exclusive range, got an inclusive one".into(),
+            ));
+        }
+        Ok(start..end)
+    }
+}
+
+/// `{ start, end, inclusive: true }`.
+impl<'rt, T: IntoJs<'rt>> IntoJs<'rt> for RangeInclusive<T> {
+    fn into_js(self, rt: &'rt Runtime) -> Result<Value<'rt>> {
+        let (start, end) = self.into_inner();
+        let obj = Object::new(rt)?;
+        obj.set("start", start.into_js(rt)?)?;
+        obj.set("end", end.into_js(rt)?)?;
+        obj.set("inclusive", Value::Bool(true))?;
+        Ok(obj.into())
+    }
+}
+
+/// Reads `{ start, end, inclusive: true }`.
+impl<'rt, T: FromJs<'rt>> FromJs<'rt> for RangeInclusive<T> {
+    fn from_js(value: &Value<'rt>) -> Result<Self> {
+        let (start, end, inclusive) = range_bounds(value)?;
+        if !inclusive {
+            return Err(Error::TypeError(
+                "expected an inclusive range, got an exclusive one".into(),
+            ));
+        }
+        Ok(start..=end)
+    }
+}
+
+/// Reads `start`, `end` and whether `inclusive` is `true`.
+fn range_bounds<'rt, T: FromJs<'rt>>(value: &Value<'rt>) -> Result<(T, T, bool)> {
+    let obj = value.clone().into_object()?;
+    let start = T::from_js(&obj.get("start")?).map_err(|err| err.at(".start"))?;
+    let end = T::from_js(&obj.get("end")?).map_err(|err| err.at(".end"))?;
+    let inclusive = obj.get("inclusive")?.as_bool() == Some(true);
+    Ok((start, end, inclusive))
+}
+
+macro_rules! impl_handle_conversions {
+    ($($ty:ident => $into:ident),*) => {$(
+        impl<'rt> IntoJs<'rt> for $ty<'rt> {
+            fn into_js(self, _rt: &'rt Runtime) -> Result<Value<'rt>> {
+                Ok(self.into())
+            }
+        }
+
+        impl<'rt> IntoJs<'rt> for &$ty<'rt> {
+            fn into_js(self, _rt: &'rt Runtime) -> Result<Value<'rt>> {
+                Ok(self.clone().into())
+            }
+        }
+
+        impl<'rt> FromJs<'rt> for $ty<'rt> {
+            fn from_js(value: &Value<'rt>) -> Result<Self> {
+                value.clone().$into()
+            }
+        }
+    )*};
+}
+
+impl_handle_conversions!(
+    JsString => into_string,
+    Symbol => into_symbol,
+    BigInt => into_bigint,
+    Object => into_object,
+    Array => into_array,
+    ArrayBuffer => into_array_buffer,
+    Function => into_function,
+    Promise => into_promise,
+    JsMap => into_map,
+    JsSet => into_set
+);
+
+impl<'rt, T: TypedArrayElement> IntoJs<'rt> for TypedArray<'rt, T> {
+    fn into_js(self, _rt: &'rt Runtime) -> Result<Value<'rt>> {
+        Ok(self.into())
+    }
+}
+
+impl<'rt, T: TypedArrayElement> FromJs<'rt> for TypedArray<'rt, T> {
+    fn from_js(value: &Value<'rt>) -> Result<Self> {
+        value.clone().into_typed_array()
+    }
+}
+
+/// Vectors convert to and from JS arrays, element by element.
+impl<'rt, T: IntoJs<'rt>> IntoJs<'rt> for Vec<T> {
+    fn into_js(self, rt: &'rt Runtime) -> Result<Value<'rt>> {
+        let array = Array::new(rt, self.len())?;
+        for (i, item) in self.into_iter().enumerate() {
+            array.set(i, item.into_js(rt)?)?;
+        }
+        Ok(array.into())
+    }
+}
+
+impl<'rt, T: FromJs<'rt>> FromJs<'rt> for Vec<T> {
+    fn from_js(value: &Value<'rt>) -> Result<Self> {
+        value.clone().into_array()?.to_vec()
+    }
+}
+
+/// Slices convert like vectors, cloning each element.
+impl<'rt, T: IntoJs<'rt> + Clone> IntoJs<'rt> for &[T] {
+    fn into_js(self, rt: &'rt Runtime) -> Result<Value<'rt>> {
+        let array = Array::new(rt, self.len())?;
+        for (i, item) in self.iter().enumerate() {
+            array.set(i, item.clone().into_js(rt)?)?;
+        }
+        Ok(array.into())
+    }
+}
+
+impl<'rt, T: FromJs<'rt>> FromJs<'rt> for Box<[T]> {
+    fn from_js(value: &Value<'rt>) -> Result<Self> {
+        Vec::from_js(value).map(Vec::into_boxed_slice)
+    }
+}
+
+/// Tuples convert to arrays of the same length; other lengths fail to
+/// convert back.
+macro_rules! impl_tuple_conversions {
+    ($(($len:expr; $($ty:ident $idx:tt),+)),* $(,)?) => {$(
+        impl<'rt, $($ty: IntoJs<'rt>),+> IntoJs<'rt> for ($($ty,)+) {
+            fn into_js(self, rt: &'rt Runtime) -> Result<Value<'rt>> {
+                let array = Array::new(rt, $len)?;
+                $(array.set($idx, self.$idx.into_js(rt)?)?;)+
+                Ok(array.into())
+            }
+        }
+
+        impl<'rt, $($ty: FromJs<'rt>),+> FromJs<'rt> for ($($ty,)+) {
+            fn from_js(value: &Value<'rt>) -> Result<Self> {
+                let array = crate::derive::tuple(value, $len)?;
+                Ok(($(
+                    $ty::from_js(&array.get($idx)?)
+                        .map_err(|err| err.at(concat!("[", stringify!($idx), "]")))?,
+                )+))
+            }
+        }
+    )*};
+}
+
+impl_tuple_conversions!(
+    (1; T0 0),
+    (2; T0 0, T1 1),
+    (3; T0 0, T1 1, T2 2),
+    (4; T0 0, T1 1, T2 2, T3 3),
+    (5; T0 0, T1 1, T2 2, T3 3, T4 4),
+    (6; T0 0, T1 1, T2 2, T3 3, T4 4, T5 5),
+    (7; T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6),
+    (8; T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7),
+);
+
+/// `None` converts to `null`; both `null` and `undefined` read as `None`.
+impl<'rt, T: IntoJs<'rt>> IntoJs<'rt> for Option<T> {
+    fn into_js(self, rt: &'rt Runtime) -> Result<Value<'rt>> {
+        match self {
+            Some(v) => v.into_js(rt),
+            None => Ok(Value::Null),
+        }
+    }
+}
+
+impl<'rt, T: FromJs<'rt>> FromJs<'rt> for Option<T> {
+    fn from_js(value: &Value<'rt>) -> Result<Self> {
+        match value {
+            Value::Undefined | Value::Null => Ok(None),
+            other => T::from_js(other).map(Some),
+        }
+    }
+}
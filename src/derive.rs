@@ -0,0 +1,59 @@
+//! Support code for the derive macros and `hermes_op`. Not public API.
+
+use crate::{Array, Error, FromJs, Object, Result, Value};
+
+pub fn expected(what: &str, got: &Value) -> Error {
+    Error::expected(what, got)
+}
+
+/// Adds the field or index `segment` to the path of a conversion error.
+pub fn at(err: Error, segment: &str) -> Error {
+    err.at(segment)
+}
+
+/// Names the op parameter a conversion error came from, e.g.
+/// `argument 1 ('b'): expected number, got undefined`.
+pub fn argument(err: Error, index: usize, name: &str) -> Error {
+    match err {
+        Error::TypeError(msg) => {
+            Error::TypeError(format!("argument {} ('{}'): {}", index, name, msg))
+        }
+        other => other,
+    }
+}
+
+/// Reads `value` as an array of exactly `len` elements.
+pub fn tuple<'rt>(value: &Value<'rt>, len: usize) -> Result<Array<'rt>> {
+    let array = value.clone().into_array()?;
+    if array.len() != len {
+        return Err(Error::TypeError(format!(
+            "expected an array of length {}, got length {}",
+            len,
+            array.len()
+        )));
+    }
+    Ok(array)
+}
+
+/// Splits an externally tagged enum value `{ "Name": payload }`.
+pub fn external_variant<'rt>(obj: &Object<'rt>, ty: &str) -> Result<(String, Value<'rt>)> {
+    let names = obj.property_names()?;
+    if names.len() != 1 {
+        return Err(Error::TypeError(format!(
+            "expected an object with a single key for enum `{}`, got {} keys",
+            ty,
+            names.len()
+        )));
+    }
+    let name = String::from_js(&names.get(0)?)?;
+    let payload = obj.get(&name)?;
+    Ok((name, payload))
+}
+
+pub fn unknown_variant(name: &str, ty: &str) -> Error {
+    Error::TypeError(format!("unknown variant `{}` of enum `{}`", name, ty))
+}
+
+pub fn missing_tag(tag: &str, ty: &str) -> Error {
+    Error::TypeError(format!("missing tag field `{}` of enum `{}`", tag, ty))
+}
@@ -0,0 +1,394 @@
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::{fmt, io};
+
+use libhermesabi_sys::*;
+
+use crate::buffer::GrowableBuffer;
+use crate::{FromJs, JsString, Object, Runtime, Value};
+
+/// Result type used throughout the crate.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Errors produced by the runtime or by value conversions.
+#[derive(Debug)]
+pub enum Error {
+    /// A JavaScript `Error` object was thrown.
+    JsError {
+        /// The error's `name`, e.g. `"TypeError"`.
+        name: String,
+        message: String,
+        /// The engine's `stack` string, if the error has one.
+        stack: Option<String>,
+    },
+    /// A JavaScript value other than an `Error` was thrown. Holds
+    /// `String(exception)`.
+    JsException(String),
+    /// A value did not have the expected type.
+    TypeError(String),
+    /// Any other failure reported by the engine or the wrapper.
+    RuntimeError(String),
+    /// Reading a script or other input failed.
+    Io(io::Error),
+}
+
+impl Error {
+    /// The message carried by the error, without the variant prefix.
+    pub fn message(&self) -> Cow<'_, str> {
+        match self {
+            Error::JsError { message, .. } => Cow::Borrowed(message),
+            Error::JsException(msg) | Error::TypeError(msg) | Error::RuntimeError(msg) => {
+                Cow::Borrowed(msg)
+            }
+            Error::Io(err) => Cow::Owned(err.to_string()),
+        }
+    }
+
+    /// The JS error constructor used when this error is thrown back into JS.
+    pub fn js_kind(&self) -> JsErrorKind {
+        match self {
+            Error::TypeError(_) => JsErrorKind::TypeError,
+            Error::JsError { name, .. } => {
+                JsErrorKind::from_name(name).unwrap_or(JsErrorKind::Error)
+            }
+            _ => JsErrorKind::Error,
+        }
+    }
+
+    /// Prefixes a conversion error with the field or index it occurred at,
+    /// e.g. `.start` or `[2]`. Segments added by outer values go in front,
+    /// giving `at .start.x: expected number, got string`.
+    pub(crate) fn at(self, segment: &str) -> Self {
+        match self {
+            Error::TypeError(msg) => Error::TypeError(match msg.strip_prefix("at ") {
+                Some(rest) => format!("at {}{}", segment, rest),
+                None => format!("at {}: {}", segment, msg),
+            }),
+            other => other,
+        }
+    }
+
+    pub(crate) fn expected(what: &str, got: &Value) -> Self {
+        Error::TypeError(format!("expected {}, got {}", what, got.kind().name()))
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::JsError { name, message, .. } if message.is_empty() => {
+                write!(f, "JavaScript exception: {}", name)
+            }
+            Error::JsError { name, message, .. } => {
+                write!(f, "JavaScript exception: {}: {}", name, message)
+            }
+            Error::JsException(msg) => write!(f, "JavaScript exception: {}", msg),
+            Error::TypeError(msg) => write!(f, "type error: {}", msg),
+            Error::RuntimeError(msg) => write!(f, "runtime error: {}", msg),
+            Error::Io(err) => write!(f, "io error: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+/// `io::Error` is not `Clone`, so a cloned [`Error::Io`] keeps the kind and
+/// message but drops the OS error code and source.
+impl Clone for Error {
+    fn clone(&self) -> Self {
+        match self {
+            Error::JsError {
+                name,
+                message,
+                stack,
+            } => Error::JsError {
+                name: name.clone(),
+                message: message.clone(),
+                stack: stack.clone(),
+            },
+            Error::JsException(msg) => Error::JsException(msg.clone()),
+            Error::TypeError(msg) => Error::TypeError(msg.clone()),
+            Error::RuntimeError(msg) => Error::RuntimeError(msg.clone()),
+            Error::Io(err) => Error::Io(io::Error::new(err.kind(), err.to_string())),
+        }
+    }
+}
+
+/// [`Error::Io`] values are equal if their kinds and messages are.
+impl PartialEq for Error {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (
+                Error::JsError {
+                    name,
+                    message,
+                    stack,
+                },
+                Error::JsError {
+                    name: other_name,
+                    message: other_message,
+                    stack: other_stack,
+                },
+            ) => name == other_name && message == other_message && stack == other_stack,
+            (Error::JsException(a), Error::JsException(b))
+            | (Error::TypeError(a), Error::TypeError(b))
+            | (Error::RuntimeError(a), Error::RuntimeError(b)) => a == b,
+            (Error::Io(a), Error::Io(b)) => a.kind() == b.kind() && a.to_string() == b.to_string(),
+            _ => false,
+        }
+    }
+}
+
+/// The built-in JS error constructors a Rust error can be thrown as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum JsErrorKind {
+    Error,
+    TypeError,
+    RangeError,
+    ReferenceError,
+    SyntaxError,
+    EvalError,
+    URIError,
+}
+
+impl JsErrorKind {
+    /// Name of the global constructor, e.g. `"RangeError"`.
+    pub fn name(self) -> &'static str {
+        match self {
+            JsErrorKind::Error => "Error",
+            JsErrorKind::TypeError => "TypeError",
+            JsErrorKind::RangeError => "RangeError",
+            JsErrorKind::ReferenceError => "ReferenceError",
+            JsErrorKind::SyntaxError => "SyntaxError",
+            JsErrorKind::EvalError => "EvalError",
+            JsErrorKind::URIError => "URIError",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        [
+            JsErrorKind::Error,
+            JsErrorKind::TypeError,
+            JsErrorKind::RangeError,
+            JsErrorKind::ReferenceError,
+            JsErrorKind::SyntaxError,
+            JsErrorKind::EvalError,
+            JsErrorKind::URIError,
+        ]
+        .into_iter()
+        .find(|kind| kind.name() == name)
+    }
+}
+
+pub(crate) fn is_error_ptr(ptr_or_error: usize) -> bool {
+    ptr_or_error & 1 != 0
+}
+
+pub(crate) fn error_code(ptr_or_error: usize) -> HermesABIErrorCode {
+    (ptr_or_error >> 2) as HermesABIErrorCode
+}
+
+/// Checks a `*OrError` pointer, turning an error into the pending exception.
+pub(crate) fn check_pointer(
+    rt: &Runtime,
+    ptr_or_error: usize,
+) -> Result<*mut HermesABIManagedPointer> {
+    if is_error_ptr(ptr_or_error) {
+        return Err(take_error(rt, error_code(ptr_or_error)));
+    }
+    Ok(ptr_or_error as *mut HermesABIManagedPointer)
+}
+
+pub(crate) fn check_value(rt: &Runtime, res: HermesABIValueOrError) -> Result<Value<'_>> {
+    if res.value.kind == HermesABIValueKind_HermesABIValueKindError {
+        return Err(take_error(rt, unsafe { res.value.data.error }));
+    }
+    Ok(unsafe { Value::from_raw(rt, res.value) })
+}
+
+pub(crate) fn check_void(rt: &Runtime, res: HermesABIVoidOrError) -> Result<()> {
+    if is_error_ptr(res.void_or_error) {
+        return Err(take_error(rt, error_code(res.void_or_error)));
+    }
+    Ok(())
+}
+
+pub(crate) fn check_bool(rt: &Runtime, res: HermesABIBoolOrError) -> Result<bool> {
+    if is_error_ptr(res.bool_or_error) {
+        return Err(take_error(rt, error_code(res.bool_or_error)));
+    }
+    Ok(res.bool_or_error >> 2 != 0)
+}
+
+pub(crate) fn check_size(rt: &Runtime, res: HermesABISizeTOrError) -> Result<usize> {
+    if res.is_error {
+        return Err(take_error(rt, unsafe { res.data.error } as HermesABIErrorCode));
+    }
+    Ok(unsafe { res.data.val })
+}
+
+pub(crate) fn check_u8_ptr(rt: &Runtime, res: HermesABIUint8PtrOrError) -> Result<*mut u8> {
+    if res.is_error {
+        return Err(take_error(rt, unsafe { res.data.error } as HermesABIErrorCode));
+    }
+    Ok(unsafe { res.data.val })
+}
+
+/// Returns the exception raised from Rust by [`Runtime::throw_error`], if
+/// any, at an `eval*` or call boundary outside host functions. Inside one it
+/// stays pending for `settle_host_call` to throw into JS.
+pub(crate) fn report_thrown(rt: &Runtime) -> Result<()> {
+    if rt.js_stack_depth() > 0 {
+        return Ok(());
+    }
+    match take_thrown(rt) {
+        Some(err) => Err(err),
+        None => Ok(()),
+    }
+}
+
+/// Takes the exception raised from Rust by [`Runtime::throw_error`], if any.
+pub(crate) fn take_thrown(rt: &Runtime) -> Option<Error> {
+    let raw = rt.state.thrown.take()?;
+    let value = unsafe { Value::from_raw(rt, raw) };
+    Some(thrown_error(rt, &value))
+}
+
+/// Clears the pending error of the given kind and converts it to an [`Error`].
+pub(crate) fn take_error(rt: &Runtime, code: HermesABIErrorCode) -> Error {
+    if code == HermesABIErrorCode_HermesABIErrorCodeJSError {
+        let raw = unsafe { abi_call!(rt, get_and_clear_js_error_value) };
+        let value = unsafe { Value::from_raw(rt, raw) };
+        thrown_error(rt, &value)
+    } else {
+        let mut buf = GrowableBuffer::new();
+        unsafe { abi_call!(rt, get_and_clear_native_exception_message, buf.as_abi()) };
+        Error::RuntimeError(buf.into_string_lossy())
+    }
+}
+
+thread_local! {
+    static EXTRACTING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Converts a thrown value: [`Error::JsError`] for `Error` objects,
+/// [`Error::JsException`] for anything else.
+pub(crate) fn thrown_error(rt: &Runtime, value: &Value) -> Error {
+    if let Value::Object(object) = value {
+        if let Some(err) = js_error_fields(rt, object) {
+            return err;
+        }
+    }
+    Error::JsException(extract_error_message(rt, value))
+}
+
+/// Reads `name`, `message` and `stack` if `object` is an `Error`. Reading
+/// can itself throw; that, or a nested call, gives `None`.
+fn js_error_fields(rt: &Runtime, object: &Object) -> Option<Error> {
+    if EXTRACTING.with(|flag| flag.replace(true)) {
+        return None;
+    }
+    let fields = (|| -> Result<Option<Error>> {
+        if !object.instance_of(&rt.builtin("Error")?)? {
+            return Ok(None);
+        }
+        let stack = match object.get("stack")? {
+            Value::String(stack) => Some(stack.to_rust_string()?),
+            _ => None,
+        };
+        Ok(Some(Error::JsError {
+            name: String::from_js(&object.get("name")?)?,
+            message: String::from_js(&object.get("message")?)?,
+            stack,
+        }))
+    })();
+    EXTRACTING.with(|flag| flag.set(false));
+    fields.ok().flatten()
+}
+
+/// Formats a thrown value the way `String(value)` would.
+///
+/// Stringifying can itself throw; nested failures fall back to a placeholder
+/// instead of recursing.
+pub(crate) fn extract_error_message(rt: &Runtime, value: &Value) -> String {
+    if let Value::String(s) = value {
+        if let Ok(msg) = s.to_rust_string() {
+            return msg;
+        }
+    }
+    if EXTRACTING.with(|flag| flag.replace(true)) {
+        return String::from("<unprintable exception>");
+    }
+    let msg = (|| {
+        let string_fn = rt.global().get("String")?.into_function()?;
+        string_fn
+            .call_internal(std::slice::from_ref(value))?
+            .into_string()?
+            .to_rust_string()
+    })();
+    EXTRACTING.with(|flag| flag.set(false));
+    msg.unwrap_or_else(|_| String::from("<unprintable exception>"))
+}
+
+/// Sets `err` as the runtime's pending exception, thrown as a `kind` JS error.
+///
+/// Falls back to a native exception message if the error object cannot be
+/// constructed. Returns the error code describing what was set.
+pub(crate) fn set_pending_error(
+    rt: &Runtime,
+    err: &Error,
+    kind: JsErrorKind,
+) -> HermesABIErrorCode {
+    let thrown = (|| {
+        let ctor = rt.builtin(kind.name())?;
+        let message = JsString::new(rt, &err.message())?;
+        ctor.call_as_constructor_internal(&[Value::String(message)])
+    })();
+    match thrown {
+        Ok(value) => {
+            let raw = value.as_raw();
+            unsafe { abi_call!(rt, set_js_error_value, &raw) };
+            HermesABIErrorCode_HermesABIErrorCodeJSError
+        }
+        Err(_) => {
+            let msg = err.message();
+            unsafe {
+                abi_call!(
+                    rt,
+                    set_native_exception_message,
+                    msg.as_ptr().cast(),
+                    msg.len()
+                )
+            };
+            HermesABIErrorCode_HermesABIErrorCodeNativeException
+        }
+    }
+}
+
+/// The `ValueOrError` a host callback returns to signal a pending error.
+pub(crate) fn error_value(code: HermesABIErrorCode) -> HermesABIValueOrError {
+    HermesABIValueOrError {
+        value: HermesABIValue {
+            kind: HermesABIValueKind_HermesABIValueKindError,
+            data: HermesABIValue__bindgen_ty_1 { error: code },
+        },
+    }
+}
+
+/// The `*OrError` bits a host callback returns to signal a pending error.
+pub(crate) fn error_bits(code: HermesABIErrorCode) -> usize {
+    ((code as usize) << 2) | 1
+}
@@ -0,0 +1,37 @@
+use crate::Runtime;
+
+/// Language features a runtime actually provides, as reported by
+/// [`Runtime::active_features`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RuntimeFeatures {
+    /// `eval` and `new Function` can compile source at run time.
+    pub eval: bool,
+    pub proxy: bool,
+    /// The `Intl` namespace exists.
+    pub intl: bool,
+    /// Generator functions parse.
+    pub generators: bool,
+    pub promise: bool,
+    pub weak_ref: bool,
+}
+
+/// Runs `code`, treating anything but `true`, including errors, as absent.
+fn probe(rt: &Runtime, code: &str) -> bool {
+    rt.eval_internal(code, "<feature probe>")
+        .map(|value| value.as_bool() == Some(true))
+        .unwrap_or(false)
+}
+
+pub(crate) fn detect(rt: &Runtime) -> RuntimeFeatures {
+    RuntimeFeatures {
+        eval: probe(
+            rt,
+            "(function () { try { return eval('1 + 1') === 2; } catch (e) { return false; } })()",
+        ),
+        proxy: probe(rt, "typeof Proxy === 'function'"),
+        intl: probe(rt, "typeof Intl === 'object'"),
+        generators: probe(rt, "typeof function* () {} === 'function'"),
+        promise: probe(rt, "typeof Promise === 'function'"),
+        weak_ref: probe(rt, "typeof WeakRef === 'function'"),
+    }
+}
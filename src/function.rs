@@ -0,0 +1,673 @@
+use std::any::Any;
+use std::backtrace::{Backtrace, BacktraceStatus};
+use std::cell::RefCell;
+use std::panic::{self, AssertUnwindSafe};
+use std::rc::Rc;
+
+use libhermesabi_sys::*;
+
+use crate::convert::MAX_SAFE_INTEGER;
+use crate::{
+    error, FromJs, IntoJs, JsErrorKind, Object, PropNameId, Result, Runtime, RuntimeState, Value,
+    WeakFunction,
+};
+
+managed_pointer!(
+    /// A callable JavaScript object.
+    Function,
+    HermesABIFunction,
+    clone_object(HermesABIObject)
+);
+
+impl<'rt> Function<'rt> {
+    /// Creates a function backed by `func`, which receives the runtime,
+    /// `this` and the arguments as passed. `param_count` is reported as the
+    /// function's `length`.
+    ///
+    /// `func` is owned by the engine and dropped when the function is
+    /// collected. It cannot hold handles such as [`Function`], which borrow
+    /// the runtime; capture a [`WeakFunction`] from
+    /// [`downgrade`](Self::downgrade) to call back into JS.
+    pub fn from_host_fn<F>(rt: &'rt Runtime, name: &str, param_count: u32, func: F) -> Result<Self>
+    where
+        F: for<'a> Fn(&'a Runtime, &Value<'a>, &[Value<'a>]) -> Result<Value<'a>> + 'static,
+    {
+        create_host_function(rt, name, param_count, Box::new(func), None)
+    }
+
+    /// Creates a function backed by the op `O`, named after it.
+    pub fn from_op<O: HermesOp>(rt: &'rt Runtime) -> Result<Self> {
+        create_host_function(rt, O::NAME, O::ARITY, Box::new(O::call), None)
+    }
+
+    /// Creates a weak reference to the function, which host closures can
+    /// capture without keeping it alive.
+    pub fn downgrade(&self) -> Result<WeakFunction> {
+        WeakFunction::new(self)
+    }
+
+    /// Calls the function with `this` set to `undefined`.
+    ///
+    /// Outside a host function, an error pending from
+    /// [`Runtime::throw_error`] is returned instead of calling.
+    pub fn call(&self, args: &[Value<'rt>]) -> Result<Value<'rt>> {
+        self.call_with_this(&Value::Undefined, args)
+    }
+
+    pub fn call_with_this(&self, this: &Value<'rt>, args: &[Value<'rt>]) -> Result<Value<'rt>> {
+        error::report_thrown(self.rt)?;
+        self.call_with_this_internal(this, args)
+    }
+
+    /// [`call`](Self::call) for the crate's own helpers, which leave an
+    /// error pending from [`Runtime::throw_error`] for the caller's next
+    /// `eval` or call.
+    pub(crate) fn call_internal(&self, args: &[Value<'rt>]) -> Result<Value<'rt>> {
+        self.call_with_this_internal(&Value::Undefined, args)
+    }
+
+    pub(crate) fn call_with_this_internal(
+        &self,
+        this: &Value<'rt>,
+        args: &[Value<'rt>],
+    ) -> Result<Value<'rt>> {
+        let args: Vec<HermesABIValue> = args.iter().map(Value::as_raw).collect();
+        self.call_with_raw_args(this, &args)
+    }
+
+    /// Calls with arguments borrowed from live values through
+    /// [`Value::as_raw`], so callers need not clone them into a slice.
+    pub(crate) fn call_with_raw_args(
+        &self,
+        this: &Value<'rt>,
+        args: &[HermesABIValue],
+    ) -> Result<Value<'rt>> {
+        let this = this.as_raw();
+        let res = unsafe {
+            abi_call!(
+                self.rt,
+                call,
+                self.as_abi(),
+                &this,
+                args.as_ptr(),
+                args.len()
+            )
+        };
+        error::check_value(self.rt, res)
+    }
+
+    /// Starts a call whose `this` and arguments are converted from Rust
+    /// values, e.g. `func.invoke().this(&obj).arg(1.0).arg("hi").call()`.
+    pub fn invoke(&self) -> Invoke<'_, 'rt> {
+        Invoke {
+            func: self,
+            this: Ok(Value::Undefined),
+            args: Ok(Vec::new()),
+        }
+    }
+
+    /// A function that calls this one with `this` and `args` fixed before
+    /// any arguments it is given, like `Function.prototype.bind`.
+    pub fn bind(&self, this: &Value<'rt>, args: &[Value<'rt>]) -> Result<Function<'rt>> {
+        let bind = self.rt.builtin("Function.prototype.bind")?;
+        let mut bind_args = Vec::with_capacity(args.len() + 1);
+        bind_args.push(this.clone());
+        bind_args.extend_from_slice(args);
+        bind.call_with_this_internal(&self.clone().into(), &bind_args)?
+            .into_function()
+    }
+
+    /// The function's `name`, e.g. `"foo"` for `function foo() {}`, or the
+    /// name a host function was created with. Empty for anonymous
+    /// functions.
+    pub fn name(&self) -> Result<String> {
+        String::from_js(&Object::from(self.clone()).get("name")?)
+    }
+
+    /// The function's `length`: its number of declared parameters, or the
+    /// `param_count` of a host function.
+    pub fn arity(&self) -> Result<u32> {
+        u32::from_js(&Object::from(self.clone()).get("length")?)
+    }
+
+    /// Calls the function with `new`.
+    pub fn call_as_constructor(&self, args: &[Value<'rt>]) -> Result<Value<'rt>> {
+        error::report_thrown(self.rt)?;
+        self.call_as_constructor_internal(args)
+    }
+
+    pub(crate) fn call_as_constructor_internal(&self, args: &[Value<'rt>]) -> Result<Value<'rt>> {
+        let args: Vec<HermesABIValue> = args.iter().map(Value::as_raw).collect();
+        let res = unsafe {
+            abi_call!(
+                self.rt,
+                call_as_constructor,
+                self.as_abi(),
+                args.as_ptr(),
+                args.len()
+            )
+        };
+        error::check_value(self.rt, res)
+    }
+}
+
+/// A call being assembled by [`Function::invoke`].
+///
+/// Conversion errors are kept until [`call`](Self::call), which reports the
+/// first one.
+#[must_use = "the function is only called by `call`"]
+pub struct Invoke<'f, 'rt> {
+    func: &'f Function<'rt>,
+    this: Result<Value<'rt>>,
+    args: Result<Vec<Value<'rt>>>,
+}
+
+impl<'rt> Invoke<'_, 'rt> {
+    /// Sets `this`, which is `undefined` by default.
+    pub fn this(mut self, this: impl IntoJs<'rt>) -> Self {
+        self.this = this.into_js(self.func.rt);
+        self
+    }
+
+    /// Appends an argument.
+    pub fn arg(mut self, arg: impl IntoJs<'rt>) -> Self {
+        if let Ok(args) = &mut self.args {
+            match arg.into_js(self.func.rt) {
+                Ok(value) => args.push(value),
+                Err(err) => self.args = Err(err),
+            }
+        }
+        self
+    }
+
+    pub fn call(self) -> Result<Value<'rt>> {
+        self.func.call_with_this(&self.this?, &self.args?)
+    }
+}
+
+/// Converts a host function argument from JS.
+///
+/// Conversions are strict: an `f64` parameter only accepts JS numbers.
+pub trait FromJsArg<'rt>: Sized {
+    fn from_arg(value: &Value<'rt>) -> Result<Self>;
+}
+
+/// All arguments of a call, for variadic host functions.
+///
+/// An op declared as `#[hermes_op] fn log(args: Args)` receives the
+/// arguments as passed instead of converting a fixed list.
+#[derive(Clone, Copy)]
+pub struct Args<'a, 'rt> {
+    rt: &'rt Runtime,
+    args: &'a [Value<'rt>],
+}
+
+impl<'a, 'rt> Args<'a, 'rt> {
+    pub fn new(rt: &'rt Runtime, args: &'a [Value<'rt>]) -> Self {
+        Args { rt, args }
+    }
+
+    pub fn runtime(&self) -> &'rt Runtime {
+        self.rt
+    }
+
+    pub fn len(&self) -> usize {
+        self.args.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.args.is_empty()
+    }
+
+    /// Converts argument `index`; missing arguments read as `undefined`.
+    pub fn get<T: FromJsArg<'rt>>(&self, index: usize) -> Result<T> {
+        T::from_arg(self.args.get(index).unwrap_or(&Value::Undefined))
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'a, Value<'rt>> {
+        self.args.iter()
+    }
+
+    pub fn as_slice(&self) -> &'a [Value<'rt>] {
+        self.args
+    }
+}
+
+impl<'a, 'rt> IntoIterator for Args<'a, 'rt> {
+    type Item = &'a Value<'rt>;
+    type IntoIter = std::slice::Iter<'a, Value<'rt>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.args.iter()
+    }
+}
+
+/// A number argument that also accepts values JS converts to numbers, such
+/// as the string `"3"`, using JS `Number(value)` semantics.
+///
+/// Values that convert to `NaN` are rejected. Integer targets also reject
+/// fractions, infinities and numbers outside the type's range, or outside
+/// the safe integer range for 64-bit types, instead of truncating or
+/// saturating. A bare `f64` parameter stays strict and only accepts
+/// numbers.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Coerce<T>(pub T);
+
+impl<T> std::ops::Deref for Coerce<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+macro_rules! impl_coerce_float {
+    ($($ty:ty),*) => {$(
+        impl<'rt> FromJsArg<'rt> for Coerce<$ty> {
+            fn from_arg(value: &Value<'rt>) -> Result<Self> {
+                coerce_to_number(value).map(|n| Coerce(n as $ty))
+            }
+        }
+    )*};
+}
+
+impl_coerce_float!(f64, f32);
+
+macro_rules! impl_coerce_integer {
+    ($($ty:ty),*) => {$(
+        impl<'rt> FromJsArg<'rt> for Coerce<$ty> {
+            fn from_arg(value: &Value<'rt>) -> Result<Self> {
+                let n = coerce_to_number(value)?;
+                if n.fract() != 0.0
+                    || n < <$ty>::MIN as f64
+                    || n > <$ty>::MAX as f64
+                    || n.abs() > MAX_SAFE_INTEGER
+                {
+                    return Err(crate::Error::TypeError(format!(
+                        "expected an integer for {}, got {}",
+                        stringify!($ty),
+                        n
+                    )));
+                }
+                Ok(Coerce(n as $ty))
+            }
+        }
+    )*};
+}
+
+impl_coerce_integer!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+fn coerce_to_number(value: &Value<'_>) -> Result<f64> {
+    let n = match (value, value.runtime()) {
+        (Value::Number(n), _) => *n,
+        (_, Some(rt)) => {
+            let number = rt
+                .builtin("Number")?
+                .call_internal(std::slice::from_ref(value))?;
+            number.as_number().unwrap_or(f64::NAN)
+        }
+        (Value::Bool(b), None) => f64::from(u8::from(*b)),
+        (Value::Null, None) => 0.0,
+        _ => f64::NAN,
+    };
+    if n.is_nan() {
+        return Err(crate::Error::TypeError(format!(
+            "cannot convert {} to a number",
+            value.type_of()
+        )));
+    }
+    Ok(n)
+}
+
+/// Converts a host function's return value to JS.
+pub trait IntoJsRet<'rt> {
+    fn into_ret(self, rt: &'rt Runtime) -> Result<Value<'rt>>;
+}
+
+macro_rules! impl_js_arg_and_ret {
+    ($($ty:ty),*) => {$(
+        impl<'rt> FromJsArg<'rt> for $ty {
+            fn from_arg(value: &Value<'rt>) -> Result<Self> {
+                <$ty>::from_js(value)
+            }
+        }
+
+        impl<'rt> IntoJsRet<'rt> for $ty {
+            fn into_ret(self, rt: &'rt Runtime) -> Result<Value<'rt>> {
+                self.into_js(rt)
+            }
+        }
+    )*};
+}
+
+impl_js_arg_and_ret!(f64, bool, String, char, i32);
+
+/// `undefined` and `null`, including omitted trailing arguments, read as
+/// `None`.
+impl<'rt, T: FromJsArg<'rt>> FromJsArg<'rt> for Option<T> {
+    fn from_arg(value: &Value<'rt>) -> Result<Self> {
+        match value {
+            Value::Undefined | Value::Null => Ok(None),
+            other => T::from_arg(other).map(Some),
+        }
+    }
+}
+
+impl<'rt> FromJsArg<'rt> for Value<'rt> {
+    fn from_arg(value: &Value<'rt>) -> Result<Self> {
+        Ok(value.clone())
+    }
+}
+
+impl<'rt> FromJsArg<'rt> for Object<'rt> {
+    fn from_arg(value: &Value<'rt>) -> Result<Self> {
+        value.clone().into_object()
+    }
+}
+
+impl<'rt> IntoJsRet<'rt> for () {
+    fn into_ret(self, _rt: &'rt Runtime) -> Result<Value<'rt>> {
+        Ok(Value::Undefined)
+    }
+}
+
+impl<'rt> IntoJsRet<'rt> for Value<'rt> {
+    fn into_ret(self, _rt: &'rt Runtime) -> Result<Value<'rt>> {
+        Ok(self)
+    }
+}
+
+/// `None` returns `null`.
+impl<'rt, T: IntoJsRet<'rt>> IntoJsRet<'rt> for Option<T> {
+    fn into_ret(self, rt: &'rt Runtime) -> Result<Value<'rt>> {
+        match self {
+            Some(v) => v.into_ret(rt),
+            None => Ok(Value::Null),
+        }
+    }
+}
+
+impl<'rt, T: IntoJsRet<'rt>> IntoJsRet<'rt> for Result<T> {
+    fn into_ret(self, rt: &'rt Runtime) -> Result<Value<'rt>> {
+        self?.into_ret(rt)
+    }
+}
+
+/// A Rust function exported to JS, generated by [`hermes_op`].
+///
+/// [`hermes_op`]: crate::hermes_op
+pub trait HermesOp: 'static {
+    /// Name of the JS function.
+    const NAME: &'static str;
+    /// Number of declared parameters, reported as the function's `length`.
+    const ARITY: u32;
+
+    fn call<'rt>(rt: &'rt Runtime, this: &Value<'rt>, args: &[Value<'rt>]) -> Result<Value<'rt>>;
+}
+
+/// A Rust closure that can be registered as a JS function.
+///
+/// Implemented for `Fn` closures of up to eight arguments whose parameters
+/// implement [`FromJsArg`] and whose result implements [`IntoJsRet`]. Missing
+/// arguments are passed as `undefined`.
+pub trait IntoHostFunction<Args>: 'static {
+    /// Number of declared parameters, reported as the function's `length`.
+    const ARITY: u32;
+
+    #[doc(hidden)]
+    fn call_host<'rt>(&self, rt: &'rt Runtime, args: &[Value<'rt>]) -> Result<Value<'rt>>;
+}
+
+macro_rules! impl_into_host_function {
+    ($arity:expr; $($arg:ident),*) => {
+        impl<F, R, $($arg,)*> IntoHostFunction<($($arg,)*)> for F
+        where
+            F: Fn($($arg),*) -> R + 'static,
+            R: for<'rt> IntoJsRet<'rt>,
+            $($arg: for<'rt> FromJsArg<'rt>,)*
+        {
+            const ARITY: u32 = $arity;
+
+            #[allow(non_snake_case, unused_mut, unused_variables)]
+            fn call_host<'rt>(&self, rt: &'rt Runtime, args: &[Value<'rt>]) -> Result<Value<'rt>> {
+                let mut args = args.iter();
+                $(
+                    let $arg = match args.next() {
+                        Some(value) => $arg::from_arg(value)?,
+                        None => $arg::from_arg(&Value::Undefined)?,
+                    };
+                )*
+                (self)($($arg),*).into_ret(rt)
+            }
+        }
+    };
+}
+
+impl_into_host_function!(0;);
+impl_into_host_function!(1; A0);
+impl_into_host_function!(2; A0, A1);
+impl_into_host_function!(3; A0, A1, A2);
+impl_into_host_function!(4; A0, A1, A2, A3);
+impl_into_host_function!(5; A0, A1, A2, A3, A4);
+impl_into_host_function!(6; A0, A1, A2, A3, A4, A5);
+impl_into_host_function!(7; A0, A1, A2, A3, A4, A5, A6);
+impl_into_host_function!(8; A0, A1, A2, A3, A4, A5, A6, A7);
+
+type HostFn = dyn for<'rt> Fn(&'rt Runtime, &Value<'rt>, &[Value<'rt>]) -> Result<Value<'rt>>;
+
+/// Heap state behind a host function. `base` must stay the first field so the
+/// engine's `HermesABIHostFunction` pointer can be cast back.
+#[repr(C)]
+struct HostFunction {
+    base: HermesABIHostFunction,
+    func: Box<HostFn>,
+    /// Error constructor used for every `Err`, overriding [`Error::js_kind`].
+    ///
+    /// [`Error::js_kind`]: crate::Error::js_kind
+    error_kind: Option<JsErrorKind>,
+    state: Rc<RuntimeState>,
+}
+
+/// Counts a host call in [`Runtime::js_stack_depth`] for as long as it lives.
+pub(crate) struct DepthGuard<'a>(&'a RuntimeState);
+
+impl<'a> DepthGuard<'a> {
+    pub(crate) fn enter(state: &'a RuntimeState) -> Self {
+        state.host_depth.set(state.host_depth.get() + 1);
+        DepthGuard(state)
+    }
+
+    /// Fails if this call goes past [`Runtime::set_host_reentrancy_limit`].
+    pub(crate) fn check_limit(&self) -> Result<()> {
+        match self.0.host_depth_limit.get() {
+            Some(limit) if self.0.host_depth.get() > limit => Err(crate::Error::RuntimeError(
+                "host reentrancy limit exceeded".into(),
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Drop for DepthGuard<'_> {
+    fn drop(&mut self) {
+        self.0.host_depth.set(self.0.host_depth.get() - 1);
+    }
+}
+
+static HOST_FUNCTION_VTABLE: HermesABIHostFunctionVTable = HermesABIHostFunctionVTable {
+    release: Some(release_host_function),
+    call: Some(call_host_function),
+};
+
+unsafe extern "C" fn release_host_function(func: *mut HermesABIHostFunction) {
+    drop(Box::from_raw(func as *mut HostFunction));
+}
+
+unsafe extern "C" fn call_host_function(
+    func: *mut HermesABIHostFunction,
+    rt: *mut HermesABIRuntime,
+    this: *const HermesABIValue,
+    args: *const HermesABIValue,
+    count: usize,
+) -> HermesABIValueOrError {
+    let host = &*(func as *const HostFunction);
+    let rt = Runtime::borrow_raw(rt, host.state.clone());
+    let rt: &Runtime = &rt;
+    let depth = DepthGuard::enter(&host.state);
+
+    let result = catch_host_panic(|| {
+        depth.check_limit()?;
+        let this = Value::from_raw_borrowed(rt, &*this);
+        let args: Vec<Value> = if count == 0 {
+            Vec::new()
+        } else {
+            std::slice::from_raw_parts(args, count)
+                .iter()
+                .map(|arg| Value::from_raw_borrowed(rt, arg))
+                .collect()
+        };
+        (host.func)(rt, &this, &args)
+    });
+
+    settle_host_value(rt, result, host.error_kind)
+}
+
+/// [`settle_host_call`] for callbacks returning a JS value.
+pub(crate) fn settle_host_value(
+    rt: &Runtime,
+    result: std::thread::Result<Result<Value<'_>>>,
+    error_kind: Option<JsErrorKind>,
+) -> HermesABIValueOrError {
+    match settle_host_call(rt, result, error_kind) {
+        Ok(value) => HermesABIValueOrError {
+            value: value.into_raw(),
+        },
+        Err(code) => error::error_value(code),
+    }
+}
+
+/// Turns the outcome of a host callback into what the engine expects.
+///
+/// An error raised with `Runtime::throw_error` wins over the result. Errors
+/// are thrown as `error_kind` when given, otherwise as the kind matching each
+/// [`Error`](crate::Error); panics become a plain `Error`. On failure the
+/// exception is left pending and its code returned.
+pub(crate) fn settle_host_call<T>(
+    rt: &Runtime,
+    result: std::thread::Result<Result<T>>,
+    error_kind: Option<JsErrorKind>,
+) -> std::result::Result<T, HermesABIErrorCode> {
+    if let Some(thrown) = rt.state.thrown.take() {
+        drop(result);
+        unsafe {
+            abi_call!(rt, set_js_error_value, &thrown);
+            drop(Value::from_raw(rt, thrown));
+        }
+        return Err(HermesABIErrorCode_HermesABIErrorCodeJSError);
+    }
+
+    match result {
+        Ok(Ok(value)) => Ok(value),
+        Ok(Err(err)) => {
+            let kind = error_kind.unwrap_or_else(|| err.js_kind());
+            Err(error::set_pending_error(rt, &err, kind))
+        }
+        Err(payload) => {
+            let err = crate::Error::RuntimeError(panic_message(&*payload));
+            Err(error::set_pending_error(rt, &err, JsErrorKind::Error))
+        }
+    }
+}
+
+thread_local! {
+    /// Backtrace of the host callback that last panicked on this thread,
+    /// recorded by [`catch_host_panic`].
+    static PANIC_BACKTRACE: RefCell<Option<Backtrace>> = const { RefCell::new(None) };
+}
+
+/// Runs a host callback, catching a panic for [`settle_host_call`].
+///
+/// A panic's backtrace is captured with [`Backtrace::capture`] while the
+/// callback unwinds, so it is only recorded if `RUST_BACKTRACE` is set, and
+/// it starts at the callback rather than at the panic. The process panic
+/// hook is left alone.
+pub(crate) fn catch_host_panic<T>(f: impl FnOnce() -> T) -> std::thread::Result<T> {
+    struct CaptureOnUnwind;
+
+    impl Drop for CaptureOnUnwind {
+        fn drop(&mut self) {
+            if std::thread::panicking() {
+                let backtrace = Backtrace::capture();
+                if backtrace.status() == BacktraceStatus::Captured {
+                    PANIC_BACKTRACE.with(|bt| *bt.borrow_mut() = Some(backtrace));
+                }
+            }
+        }
+    }
+
+    PANIC_BACKTRACE.with(|bt| bt.borrow_mut().take());
+    panic::catch_unwind(AssertUnwindSafe(|| {
+        let _capture = CaptureOnUnwind;
+        f()
+    }))
+}
+
+/// The message thrown into JS for a panic: the payload if it is a string,
+/// followed by the panic's backtrace when one was recorded.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    let mut message = match payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+    {
+        Some(msg) => format!("host function panicked: {}", msg),
+        None => String::from("host function panicked"),
+    };
+    if let Some(backtrace) = PANIC_BACKTRACE.with(|bt| bt.borrow_mut().take()) {
+        message.push_str(&format!("\n{}", backtrace));
+    }
+    message
+}
+
+/// Creates a JS function backed by a typed closure.
+pub(crate) fn from_closure<'rt, Args, F: IntoHostFunction<Args>>(
+    rt: &'rt Runtime,
+    name: &str,
+    func: F,
+    error_kind: Option<JsErrorKind>,
+) -> Result<Function<'rt>> {
+    let func: Box<HostFn> = Box::new(move |rt, _this, args| func.call_host(rt, args));
+    create_host_function(rt, name, F::ARITY, func, error_kind)
+}
+
+/// Creates a JS function backed by `func`. Errors are thrown as `error_kind`
+/// when given, otherwise as the kind matching each [`Error`](crate::Error).
+pub(crate) fn create_host_function<'rt>(
+    rt: &'rt Runtime,
+    name: &str,
+    param_count: u32,
+    func: Box<HostFn>,
+    error_kind: Option<JsErrorKind>,
+) -> Result<Function<'rt>> {
+    let name = PropNameId::new(rt, name)?;
+    let host = Box::into_raw(Box::new(HostFunction {
+        base: HermesABIHostFunction {
+            vtable: &HOST_FUNCTION_VTABLE,
+        },
+        func,
+        error_kind,
+        state: rt.state.clone(),
+    }));
+    // The runtime owns `host` from here on and releases it even on failure.
+    let res = unsafe {
+        abi_call!(
+            rt,
+            create_function_from_host_function,
+            name.as_abi(),
+            param_count,
+            host as *mut HermesABIHostFunction
+        )
+    };
+    let ptr = error::check_pointer(rt, res.ptr_or_error)?;
+    Ok(unsafe { Function::from_raw(rt, ptr) })
+}
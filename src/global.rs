@@ -0,0 +1,51 @@
+use std::rc::Rc;
+
+use libhermesabi_sys::*;
+
+use crate::{Error, Result, Runtime, RuntimeState, Value};
+
+/// A strong reference to a value that does not borrow the runtime, like
+/// V8's `Global`.
+///
+/// A [`Value`] already keeps what it refers to alive, but its `'rt`
+/// lifetime ties it to a borrow of the runtime. A `Global` can live in
+/// long-lived structs and `'static` closures instead, and is read back with
+/// [`get`](Self::get) given the runtime it came from.
+pub struct Global {
+    raw: HermesABIValue,
+    /// The owning runtime's state, or `None` for primitives held inline.
+    state: Option<Rc<RuntimeState>>,
+}
+
+impl Global {
+    pub fn new(value: &Value<'_>) -> Self {
+        Global {
+            state: value.runtime().map(|rt| rt.state.clone()),
+            raw: value.clone().into_raw(),
+        }
+    }
+
+    /// A new handle to the value. Fails if `rt` is not the runtime the
+    /// value belongs to.
+    pub fn get<'rt>(&self, rt: &'rt Runtime) -> Result<Value<'rt>> {
+        if let Some(state) = &self.state {
+            if !Rc::ptr_eq(state, &rt.state) {
+                return Err(Error::RuntimeError(
+                    "global handle read with a different runtime".into(),
+                ));
+            }
+        }
+        Ok(unsafe { Value::from_raw_borrowed(rt, &self.raw) })
+    }
+}
+
+impl Drop for Global {
+    fn drop(&mut self) {
+        // See `RuntimeState::released`.
+        if let Some(state) = &self.state {
+            if !state.released.get() {
+                unsafe { crate::invalidate(self.raw.data.pointer) }
+            }
+        }
+    }
+}
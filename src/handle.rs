@@ -0,0 +1,93 @@
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, Sender};
+use std::thread::{self, JoinHandle};
+
+use crate::{Error, Result, Runtime};
+
+type Job = Box<dyn FnOnce(&Runtime) + Send>;
+
+/// A `Send + Sync` handle to a [`Runtime`] living on its own thread.
+///
+/// Work is submitted as closures that run on the runtime's thread, one at a
+/// time in submission order. Closures must return owned data: `Value`s and
+/// other handles borrow the runtime and cannot leave the closure.
+///
+/// Dropping the handle finishes queued work and shuts the runtime down.
+pub struct RuntimeHandle {
+    jobs: Option<Sender<Job>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl RuntimeHandle {
+    /// Spawns a thread and creates a runtime on it.
+    pub fn spawn() -> Result<Self> {
+        let (jobs, queue) = mpsc::channel::<Job>();
+        let (ready, started) = mpsc::channel();
+        let thread = thread::Builder::new()
+            .name("hermes-runtime".into())
+            .spawn(move || {
+                let rt = match Runtime::new() {
+                    Ok(rt) => rt,
+                    Err(err) => {
+                        let _ = ready.send(Err(err));
+                        return;
+                    }
+                };
+                let _ = ready.send(Ok(()));
+                for job in queue {
+                    job(&rt);
+                }
+            })
+            .map_err(|err| {
+                Error::RuntimeError(format!("failed to spawn runtime thread: {}", err))
+            })?;
+
+        match started.recv() {
+            Ok(Ok(())) => Ok(RuntimeHandle {
+                jobs: Some(jobs),
+                thread: Some(thread),
+            }),
+            Ok(Err(err)) => Err(err),
+            Err(_) => Err(stopped()),
+        }
+    }
+
+    /// Runs `f` on the runtime's thread and waits for its result.
+    ///
+    /// A panic in `f` is propagated to the caller; the runtime keeps
+    /// serving later calls.
+    pub fn with<F, R>(&self, f: F) -> Result<R>
+    where
+        F: FnOnce(&Runtime) -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let (reply, result) = mpsc::channel::<thread::Result<R>>();
+        let job: Job = Box::new(move |rt| {
+            let _ = reply.send(panic::catch_unwind(AssertUnwindSafe(|| f(rt))));
+        });
+        self.jobs
+            .as_ref()
+            .expect("jobs sender is only taken on drop")
+            .send(job)
+            .map_err(|_| stopped())?;
+        match result.recv() {
+            Ok(Ok(value)) => Ok(value),
+            Ok(Err(payload)) => panic::resume_unwind(payload),
+            Err(_) => Err(stopped()),
+        }
+    }
+}
+
+impl Drop for RuntimeHandle {
+    fn drop(&mut self) {
+        // Closing the channel ends the job loop once queued work is done.
+        drop(self.jobs.take());
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn stopped() -> Error {
+    Error::RuntimeError("the runtime thread has stopped".into())
+}
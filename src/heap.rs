@@ -0,0 +1,79 @@
+use std::time::Duration;
+
+use crate::{Error, FromJs, Object, Result, Runtime, Value};
+
+/// Heap statistics reported by [`Runtime::heap_info`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeapInfo {
+    /// Bytes currently allocated to live and not yet collected objects.
+    pub allocated_bytes: u64,
+    /// Bytes reserved by the garbage-collected heap.
+    pub heap_size: u64,
+    /// Garbage collections run so far.
+    pub num_collections: u64,
+}
+
+/// Garbage collection timings reported by [`Runtime::gc_stats`].
+///
+/// The engine only reports cumulative times, not individual pauses, so
+/// the longest pause is not available.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GcStats {
+    /// Garbage collections run so far.
+    pub count: u64,
+    /// Wall-clock time spent in collections, i.e. the total pause.
+    pub total_pause: Duration,
+    /// CPU time spent in collections, across all GC threads.
+    pub total_cpu_time: Duration,
+}
+
+/// `HermesInternal.getInstrumentedStats()`, as the C ABI has no entry
+/// point for engine statistics.
+fn instrumented_stats(rt: &Runtime) -> Result<Object<'_>> {
+    let stats = rt.hermes_internal()?.get("getInstrumentedStats")?;
+    stats.into_function()?.call_internal(&[])?.into_object()
+}
+
+fn read_stat<T: for<'rt> FromJs<'rt>>(stats: &Object, key: &str) -> Result<T> {
+    match stats.get(key)? {
+        Value::Undefined => Err(Error::RuntimeError(format!(
+            "statistic `{}` is not reported by this runtime",
+            key
+        ))),
+        value => T::from_js(&value),
+    }
+}
+
+pub(crate) fn heap_info(rt: &Runtime) -> Result<HeapInfo> {
+    let stats = instrumented_stats(rt)?;
+    Ok(HeapInfo {
+        allocated_bytes: read_stat(&stats, "js_allocatedBytes")?,
+        heap_size: read_stat(&stats, "js_heapSize")?,
+        num_collections: read_stat(&stats, "js_numGCs")?,
+    })
+}
+
+pub(crate) fn gc_stats(rt: &Runtime) -> Result<GcStats> {
+    let stats = instrumented_stats(rt)?;
+    // Times are reported in seconds.
+    let seconds = |key| read_stat::<f64>(&stats, key).map(Duration::from_secs_f64);
+    Ok(GcStats {
+        count: read_stat(&stats, "js_numGCs")?,
+        total_pause: seconds("js_gcTime")?,
+        total_cpu_time: seconds("js_gcCPUTime")?,
+    })
+}
+
+/// Calls the global `gc` function, which Hermes installs unless the
+/// runtime was configured without it.
+pub(crate) fn collect_garbage(rt: &Runtime) -> Result<()> {
+    match rt.global().get("gc")? {
+        Value::Object(gc) if gc.is_function() => {
+            gc.into_function()?.call_internal(&[])?;
+            Ok(())
+        }
+        _ => Err(Error::RuntimeError(
+            "garbage collection is not exposed by this runtime".into(),
+        )),
+    }
+}
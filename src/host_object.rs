@@ -0,0 +1,178 @@
+use std::rc::Rc;
+
+use libhermesabi_sys::*;
+
+use crate::function::{catch_host_panic, settle_host_call, settle_host_value, DepthGuard};
+use crate::{error, Error, Object, PropNameId, Result, Runtime, RuntimeState, Value};
+
+/// A Rust value exposed to JS as an object.
+///
+/// Property reads, writes and enumeration on the object are forwarded to
+/// these methods. They take `&self`, so state changed by `set` needs
+/// interior mutability. Errors are thrown into JS like those of host
+/// functions.
+pub trait HostObject: 'static {
+    /// Reads the property `name`. Unknown properties should read as
+    /// `undefined`.
+    fn get<'rt>(&self, rt: &'rt Runtime, name: &PropNameId<'rt>) -> Result<Value<'rt>>;
+
+    /// Writes the property `name`. Throws a `TypeError` by default.
+    fn set<'rt>(&self, rt: &'rt Runtime, name: &PropNameId<'rt>, value: Value<'rt>) -> Result<()> {
+        let _ = (rt, value);
+        Err(Error::TypeError(format!(
+            "cannot assign to property `{}` of a host object",
+            name.to_rust_string()?
+        )))
+    }
+
+    /// Names listed by `Object.keys` and `for...in`. Empty by default.
+    fn property_names<'rt>(&self, rt: &'rt Runtime) -> Result<Vec<PropNameId<'rt>>> {
+        let _ = rt;
+        Ok(Vec::new())
+    }
+}
+
+/// Heap state behind a host object. `base` must stay the first field so the
+/// engine's `HermesABIHostObject` pointer can be cast back.
+#[repr(C)]
+struct HostObjectBox {
+    base: HermesABIHostObject,
+    object: Box<dyn HostObject>,
+    state: Rc<RuntimeState>,
+}
+
+/// Property names handed to the engine by `get_own_keys`. `base.props`
+/// points into `names`, which owns the pointers.
+#[repr(C)]
+struct PropNameIdList {
+    base: HermesABIPropNameIDList,
+    names: Vec<HermesABIPropNameID>,
+}
+
+static HOST_OBJECT_VTABLE: HermesABIHostObjectVTable = HermesABIHostObjectVTable {
+    release: Some(release_host_object),
+    get: Some(get_property),
+    set: Some(set_property),
+    get_own_keys: Some(get_own_keys),
+};
+
+static PROP_NAME_ID_LIST_VTABLE: HermesABIPropNameIDListVTable = HermesABIPropNameIDListVTable {
+    release: Some(release_prop_name_id_list),
+};
+
+unsafe extern "C" fn release_host_object(object: *mut HermesABIHostObject) {
+    drop(Box::from_raw(object as *mut HostObjectBox));
+}
+
+unsafe extern "C" fn release_prop_name_id_list(list: *mut HermesABIPropNameIDList) {
+    let list = Box::from_raw(list as *mut PropNameIdList);
+    for name in &list.names {
+        crate::invalidate(name.pointer);
+    }
+}
+
+/// Clones a property name borrowed from the engine.
+unsafe fn borrow_name(rt: &Runtime, name: HermesABIPropNameID) -> PropNameId<'_> {
+    let cloned = abi_call!(rt, clone_propnameid, name);
+    PropNameId::from_raw(rt, cloned.pointer)
+}
+
+unsafe extern "C" fn get_property(
+    object: *mut HermesABIHostObject,
+    rt: *mut HermesABIRuntime,
+    name: HermesABIPropNameID,
+) -> HermesABIValueOrError {
+    let host = &*(object as *const HostObjectBox);
+    let rt = Runtime::borrow_raw(rt, host.state.clone());
+    let rt: &Runtime = &rt;
+    let depth = DepthGuard::enter(&host.state);
+
+    let result = catch_host_panic(|| {
+        depth.check_limit()?;
+        host.object.get(rt, &borrow_name(rt, name))
+    });
+    settle_host_value(rt, result, None)
+}
+
+unsafe extern "C" fn set_property(
+    object: *mut HermesABIHostObject,
+    rt: *mut HermesABIRuntime,
+    name: HermesABIPropNameID,
+    value: *const HermesABIValue,
+) -> HermesABIVoidOrError {
+    let host = &*(object as *const HostObjectBox);
+    let rt = Runtime::borrow_raw(rt, host.state.clone());
+    let rt: &Runtime = &rt;
+    let depth = DepthGuard::enter(&host.state);
+
+    let result = catch_host_panic(|| {
+        depth.check_limit()?;
+        let value = Value::from_raw_borrowed(rt, &*value);
+        host.object.set(rt, &borrow_name(rt, name), value)
+    });
+    let void_or_error = match settle_host_call(rt, result, None) {
+        Ok(()) => 0,
+        Err(code) => error::error_bits(code),
+    };
+    HermesABIVoidOrError { void_or_error }
+}
+
+unsafe extern "C" fn get_own_keys(
+    object: *mut HermesABIHostObject,
+    rt: *mut HermesABIRuntime,
+) -> HermesABIPropNameIDListPtrOrError {
+    let host = &*(object as *const HostObjectBox);
+    let rt = Runtime::borrow_raw(rt, host.state.clone());
+    let rt: &Runtime = &rt;
+    let depth = DepthGuard::enter(&host.state);
+
+    let result = catch_host_panic(|| {
+        depth.check_limit()?;
+        host.object.property_names(rt)
+    });
+    let ptr_or_error = match settle_host_call(rt, result, None) {
+        Ok(names) => {
+            let names: Vec<HermesABIPropNameID> = names
+                .into_iter()
+                .map(|name| HermesABIPropNameID {
+                    pointer: name.into_raw(),
+                })
+                .collect();
+            let list = Box::new(PropNameIdList {
+                base: HermesABIPropNameIDList {
+                    vtable: &PROP_NAME_ID_LIST_VTABLE,
+                    props: names.as_ptr(),
+                    size: names.len(),
+                },
+                names,
+            });
+            Box::into_raw(list) as usize
+        }
+        Err(code) => error::error_bits(code),
+    };
+    HermesABIPropNameIDListPtrOrError { ptr_or_error }
+}
+
+/// Creates a JS object backed by `object`.
+pub(crate) fn create_host_object<'rt>(
+    rt: &'rt Runtime,
+    object: Box<dyn HostObject>,
+) -> Result<Object<'rt>> {
+    let host = Box::into_raw(Box::new(HostObjectBox {
+        base: HermesABIHostObject {
+            vtable: &HOST_OBJECT_VTABLE,
+        },
+        object,
+        state: rt.state.clone(),
+    }));
+    // The runtime owns `host` from here on and releases it even on failure.
+    let res = unsafe {
+        abi_call!(
+            rt,
+            create_object_from_host_object,
+            host as *mut HermesABIHostObject
+        )
+    };
+    let ptr = error::check_pointer(rt, res.ptr_or_error)?;
+    Ok(unsafe { Object::from_raw(rt, ptr) })
+}
@@ -0,0 +1,52 @@
+use serde_json::Value as JsonValue;
+
+use crate::{Error, FromJs, FromJsArg, IntoJs, IntoJsRet, Result, Runtime, Value};
+
+/// Builds the value with `JSON.parse`, so objects and arrays nest as they
+/// would for a JSON literal in JS.
+impl<'rt> IntoJs<'rt> for JsonValue {
+    fn into_js(self, rt: &'rt Runtime) -> Result<Value<'rt>> {
+        (&self).into_js(rt)
+    }
+}
+
+impl<'rt> IntoJs<'rt> for &JsonValue {
+    fn into_js(self, rt: &'rt Runtime) -> Result<Value<'rt>> {
+        match self {
+            JsonValue::Null => Ok(Value::Null),
+            JsonValue::Bool(b) => Ok(Value::Bool(*b)),
+            JsonValue::String(s) => s.as_str().into_js(rt),
+            other => {
+                let parse = rt.builtin("JSON.parse")?;
+                parse.call_internal(&[other.to_string().into_js(rt)?])
+            }
+        }
+    }
+}
+
+/// Reads the value through `JSON.stringify`, so `toJSON` methods apply and
+/// `undefined`, functions and symbols inside objects are skipped. A bare
+/// `undefined` reads as `null`; `NaN` and infinities read as `null`, as in
+/// JSON. Integral numbers become integer JSON numbers.
+impl<'rt> FromJs<'rt> for JsonValue {
+    fn from_js(value: &Value<'rt>) -> Result<Self> {
+        if value.is_undefined() {
+            return Ok(JsonValue::Null);
+        }
+        let json = value.to_json_string(None)?;
+        serde_json::from_str(&json)
+            .map_err(|err| Error::RuntimeError(format!("invalid JSON from JS: {}", err)))
+    }
+}
+
+impl<'rt> FromJsArg<'rt> for JsonValue {
+    fn from_arg(value: &Value<'rt>) -> Result<Self> {
+        JsonValue::from_js(value)
+    }
+}
+
+impl<'rt> IntoJsRet<'rt> for JsonValue {
+    fn into_ret(self, rt: &'rt Runtime) -> Result<Value<'rt>> {
+        self.into_js(rt)
+    }
+}
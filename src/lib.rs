@@ -1 +1,785 @@
-use libhermesabi_sys;
+//! Safe, high-level wrapper around the Hermes C ABI exposed by
+//! [`libhermesabi_sys`].
+//!
+//! # Example
+//!
+//! ```rust
+//! use rusty_hermes::{JsErrorKind, Runtime};
+//!
+//! fn main() -> rusty_hermes::Result<()> {
+//!     let rt = Runtime::new()?;
+//!
+//!     rt.set_func("add", |a: f64, b: f64| a + b)?;
+//!     rt.set_func_throwing(
+//!         "parse",
+//!         |s: String| s.parse::<f64>().map_err(|e| rusty_hermes::Error::RuntimeError(e.to_string())),
+//!         JsErrorKind::RangeError,
+//!     )?;
+//!
+//!     let sum = rt.eval("add(1, 2)")?;
+//!     assert_eq!(sum.as_number(), Some(3.0));
+//!     Ok(())
+//! }
+//! ```
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::mem::ManuallyDrop;
+use std::rc::Rc;
+
+use libhermesabi_sys::*;
+
+/// Invokes an entry of the runtime vtable, passing the runtime pointer first.
+/// Must be used inside an `unsafe` block.
+macro_rules! abi_call {
+    ($rt:expr, $name:ident $(, $arg:expr)* $(,)?) => {{
+        let rt: &$crate::Runtime = $rt;
+        (rt.vtable()
+            .$name
+            .expect(concat!("Hermes ABI is missing `", stringify!($name), "`")))(
+            rt.as_raw() $(, $arg)*
+        )
+    }};
+}
+
+/// Declares a reference-counted handle to a managed engine pointer.
+///
+/// The handle invalidates its pointer on drop and clones it through the given
+/// vtable entry.
+macro_rules! managed_pointer {
+    ($(#[$meta:meta])* $name:ident, $abi:ident, $clone:ident($clone_abi:ident)) => {
+        $(#[$meta])*
+        pub struct $name<'rt> {
+            pub(crate) ptr: *mut HermesABIManagedPointer,
+            pub(crate) rt: &'rt $crate::Runtime,
+        }
+
+        impl<'rt> $name<'rt> {
+            /// Takes ownership of `ptr`, which must be a live managed pointer
+            /// of the right kind created by `rt`.
+            pub(crate) unsafe fn from_raw(rt: &'rt $crate::Runtime, ptr: *mut HermesABIManagedPointer) -> Self {
+                $name { ptr, rt }
+            }
+
+            #[allow(dead_code)]
+            pub(crate) fn as_abi(&self) -> $abi {
+                $abi { pointer: self.ptr }
+            }
+
+            /// Releases ownership of the pointer without invalidating it.
+            #[allow(dead_code)]
+            pub(crate) fn into_raw(self) -> *mut HermesABIManagedPointer {
+                let ptr = self.ptr;
+                std::mem::forget(self);
+                ptr
+            }
+
+            /// The runtime this handle belongs to.
+            pub fn runtime(&self) -> &'rt $crate::Runtime {
+                self.rt
+            }
+        }
+
+        impl Clone for $name<'_> {
+            fn clone(&self) -> Self {
+                let cloned = unsafe { abi_call!(self.rt, $clone, $clone_abi { pointer: self.ptr }) };
+                $name { ptr: cloned.pointer, rt: self.rt }
+            }
+        }
+
+        impl Drop for $name<'_> {
+            fn drop(&mut self) {
+                unsafe { $crate::invalidate(self.ptr) }
+            }
+        }
+
+        impl std::fmt::Debug for $name<'_> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_tuple(stringify!($name)).field(&self.ptr).finish()
+            }
+        }
+    };
+}
+
+mod array;
+mod array_buffer;
+mod async_iter;
+mod bigint;
+mod buffer;
+mod collection;
+mod console;
+mod convert;
+mod derive;
+mod error;
+mod features;
+mod function;
+mod global;
+mod handle;
+mod heap;
+mod host_object;
+#[cfg(feature = "serde_json")]
+mod json;
+mod module;
+mod object;
+mod promise;
+mod propnameid;
+mod script;
+#[cfg(feature = "serde")]
+mod serde_impl;
+mod snapshot;
+mod string;
+mod symbol;
+mod typed_array;
+mod value;
+mod weak;
+
+pub use libhermesabi_sys as sys;
+
+pub use array::{Array, ArrayIter};
+pub use array_buffer::ArrayBuffer;
+pub use bigint::BigInt;
+pub use collection::{JsMap, JsSet};
+pub use console::ConsoleLevel;
+pub use convert::{FromJs, IntoJs};
+pub use error::{Error, JsErrorKind, Result};
+pub use features::RuntimeFeatures;
+pub use function::{
+    Args, Coerce, FromJsArg, Function, HermesOp, IntoHostFunction, IntoJsRet, Invoke,
+};
+pub use global::Global;
+pub use handle::RuntimeHandle;
+pub use heap::{GcStats, HeapInfo};
+pub use host_object::HostObject;
+pub use module::{ModuleLoader, ModuleRegistry};
+pub use object::{MethodChain, Object, PropertyDescriptor};
+pub use promise::Promise;
+pub use propnameid::PropNameId;
+pub use rusty_hermes_macros::{hermes_op, FromJs, HostObject, IntoJs};
+pub use script::PreparedJavaScript;
+#[cfg(feature = "serde")]
+pub use serde_impl::{from_value, to_value, Deserializer, Serializer};
+pub use snapshot::GlobalSnapshot;
+pub use string::{JsString, JsStringBuilder};
+pub use symbol::{Symbol, WellKnown};
+pub use typed_array::{
+    BigInt64Array, BigUint64Array, Float32Array, Float64Array, Int16Array, Int32Array, Int8Array,
+    TypedArray, TypedArrayElement, TypedArrayKind, TypedArrayRef, Uint16Array, Uint32Array,
+    Uint8Array,
+};
+pub use value::{Value, ValueKind};
+pub use weak::WeakFunction;
+
+use buffer::OwnedBuffer;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::derive::*;
+}
+
+/// Releases a managed pointer through its own vtable.
+pub(crate) unsafe fn invalidate(ptr: *mut HermesABIManagedPointer) {
+    if let Some(invalidate) = (*(*ptr).vtable).invalidate {
+        invalidate(ptr);
+    }
+}
+
+/// Rust-side state shared by a runtime and the host functions it owns.
+#[derive(Default)]
+pub(crate) struct RuntimeState {
+    /// Number of host function calls currently on the stack.
+    host_depth: Cell<usize>,
+    /// Deepest nesting of host calls allowed, if capped.
+    host_depth_limit: Cell<Option<usize>>,
+    /// Built-in functions looked up by [`Runtime::builtin`], keyed by path,
+    /// and helpers created by [`Runtime::cached_function`].
+    /// Invalidated when the owning runtime is dropped.
+    builtins: RefCell<HashMap<&'static str, *mut HermesABIManagedPointer>>,
+    /// The global object, fetched on first use. Owned; invalidated when
+    /// the owning runtime is dropped.
+    global: Cell<Option<*mut HermesABIManagedPointer>>,
+    /// Exception raised by [`Runtime::throw_error`] that has not been
+    /// reported yet. Owned; released if the runtime is dropped first.
+    thrown: Cell<Option<HermesABIValue>>,
+    /// Source URL used by [`Runtime::eval`], if set.
+    eval_url: RefCell<Option<String>>,
+    /// Set once the owning runtime has been released, so handles that
+    /// outlive it, like [`WeakFunction`] and [`Global`], skip invalidation.
+    released: Cell<bool>,
+}
+
+/// A Hermes runtime instance.
+///
+/// Values created by the runtime borrow it and cannot outlive it.
+pub struct Runtime {
+    raw: *mut HermesABIRuntime,
+    state: Rc<RuntimeState>,
+    /// Whether dropping this handle releases the engine runtime. Handles
+    /// rebuilt inside host callbacks borrow the runtime instead.
+    owned: bool,
+}
+
+impl Runtime {
+    /// Creates a runtime with the default configuration.
+    pub fn new() -> Result<Self> {
+        let raw = unsafe {
+            let vtable = &*get_hermes_abi_vtable();
+            let make_runtime = vtable
+                .make_hermes_runtime
+                .expect("Hermes ABI is missing `make_hermes_runtime`");
+            make_runtime(std::ptr::null())
+        };
+        if raw.is_null() {
+            return Err(Error::RuntimeError(
+                "failed to create Hermes runtime".into(),
+            ));
+        }
+        Ok(Runtime {
+            raw,
+            state: Rc::default(),
+            owned: true,
+        })
+    }
+
+    /// Wraps a runtime pointer owned by another `Runtime` handle.
+    pub(crate) unsafe fn borrow_raw(raw: *mut HermesABIRuntime, state: Rc<RuntimeState>) -> Self {
+        Runtime {
+            raw,
+            state,
+            owned: false,
+        }
+    }
+
+    /// Looks up a built-in function by its dotted path from the global
+    /// object, e.g. `"Array.prototype.indexOf"`.
+    ///
+    /// The first lookup is cached for the runtime's lifetime, so later
+    /// reassignments by scripts do not affect Rust callers.
+    pub(crate) fn builtin(&self, path: &'static str) -> Result<Function<'_>> {
+        self.cached_function(path, || {
+            let mut value = Value::Object(self.global());
+            for segment in path.split('.') {
+                value = value.into_object()?.get(segment)?;
+            }
+            value.into_function()
+        })
+    }
+
+    /// Returns the function cached under `key`, creating it with `init` on
+    /// first use. Shares the cache of [`builtin`](Self::builtin), so keys
+    /// that are not global paths should be bracketed, e.g. `"<identity>"`.
+    pub(crate) fn cached_function<'rt>(
+        &'rt self,
+        key: &'static str,
+        init: impl FnOnce() -> Result<Function<'rt>>,
+    ) -> Result<Function<'rt>> {
+        if let Some(&ptr) = self.state.builtins.borrow().get(key) {
+            let cached = ManuallyDrop::new(unsafe { Function::from_raw(self, ptr) });
+            return Ok((*cached).clone());
+        }
+        let func = init()?;
+        self.state
+            .builtins
+            .borrow_mut()
+            .insert(key, func.clone().into_raw());
+        Ok(func)
+    }
+
+    pub(crate) fn as_raw(&self) -> *mut HermesABIRuntime {
+        self.raw
+    }
+
+    pub(crate) fn vtable(&self) -> &HermesABIRuntimeVTable {
+        unsafe { &*(*self.raw).vt }
+    }
+
+    /// Evaluates `code` as a script, using the URL set with
+    /// [`set_default_eval_url`](Self::set_default_eval_url) or `"<eval>"` as
+    /// its source URL.
+    pub fn eval(&self, code: &str) -> Result<Value<'_>> {
+        let url = self.state.eval_url.borrow().clone();
+        self.eval_with_url(code, url.as_deref().unwrap_or("<eval>"))
+    }
+
+    /// Sets the source URL that [`eval`](Self::eval) reports in stack
+    /// traces, e.g. the application name.
+    pub fn set_default_eval_url(&self, url: &str) {
+        *self.state.eval_url.borrow_mut() = Some(url.to_owned());
+    }
+
+    /// Alias of [`eval_with_url`](Self::eval_with_url): evaluates `code`
+    /// with `name` as its source URL, so the stacks of errors it throws
+    /// name the script.
+    pub fn eval_named(&self, code: &str, name: &str) -> Result<Value<'_>> {
+        self.eval_with_url(code, name)
+    }
+
+    /// Evaluates `code` and converts the result, e.g.
+    /// `let n: f64 = rt.eval_as("1 + 2")?`.
+    pub fn eval_as<'rt, T: FromJs<'rt>>(&'rt self, code: &str) -> Result<T> {
+        T::from_js(&self.eval(code)?)
+    }
+
+    /// Evaluates `code` as a script, reporting `url` in stack traces.
+    ///
+    /// Outside a host function, an error pending from
+    /// [`throw_error`](Self::throw_error) is returned instead of running
+    /// the script; this holds for every `eval*` method.
+    pub fn eval_with_url(&self, code: &str, url: &str) -> Result<Value<'_>> {
+        error::report_thrown(self)?;
+        self.eval_internal(code, url)
+    }
+
+    /// [`eval_with_url`](Self::eval_with_url) for the crate's own helpers,
+    /// which leave an error pending from [`throw_error`](Self::throw_error)
+    /// for the caller's next `eval` or call.
+    pub(crate) fn eval_internal(&self, code: &str, url: &str) -> Result<Value<'_>> {
+        let buffer = OwnedBuffer::into_abi(code.as_bytes().to_vec());
+        let res = unsafe {
+            abi_call!(
+                self,
+                evaluate_javascript_source,
+                buffer,
+                url.as_ptr().cast(),
+                url.len()
+            )
+        };
+        error::check_value(self, res)
+    }
+
+    /// Whether `data` starts with a Hermes bytecode header. Does not check
+    /// that the bytecode version matches this engine.
+    pub fn is_hermes_bytecode(data: &[u8]) -> bool {
+        unsafe {
+            let vtable = &*get_hermes_abi_vtable();
+            let is_bytecode = vtable
+                .is_hermes_bytecode
+                .expect("Hermes ABI is missing `is_hermes_bytecode`");
+            is_bytecode(data.as_ptr(), data.len())
+        }
+    }
+
+    /// Evaluates precompiled Hermes bytecode, reporting `url` in stack
+    /// traces.
+    ///
+    /// The C ABI cannot compile source to bytecode, so `bytecode` comes from
+    /// running `hermesc -emit-binary` at build time with the same Hermes
+    /// version. Data without a bytecode header is an
+    /// [`Error::RuntimeError`]; the engine rejects bytecode from another
+    /// version.
+    pub fn evaluate_bytecode(&self, bytecode: &[u8], url: &str) -> Result<Value<'_>> {
+        error::report_thrown(self)?;
+        if !Self::is_hermes_bytecode(bytecode) {
+            return Err(Error::RuntimeError(format!("{}: not Hermes bytecode", url)));
+        }
+        let buffer = OwnedBuffer::into_abi(bytecode.to_vec());
+        let res = unsafe {
+            abi_call!(
+                self,
+                evaluate_hermes_bytecode,
+                buffer,
+                url.as_ptr().cast(),
+                url.len()
+            )
+        };
+        error::check_value(self, res)
+    }
+
+    /// Evaluates `data` as bytecode if it has a Hermes bytecode header, and
+    /// otherwise as UTF-8 source. Invalid UTF-8 source is an
+    /// [`Error::RuntimeError`].
+    pub fn eval_bytes(&self, data: &[u8], url: &str) -> Result<Value<'_>> {
+        if Self::is_hermes_bytecode(data) {
+            return self.evaluate_bytecode(data, url);
+        }
+        let code = std::str::from_utf8(data).map_err(|err| {
+            Error::RuntimeError(format!("{}: invalid UTF-8 source: {}", url, err))
+        })?;
+        self.eval_with_url(code, url)
+    }
+
+    /// Reads the file at `path` and evaluates it with [`eval_bytes`],
+    /// using the path as the URL in stack traces. A read failure is an
+    /// [`Error::Io`].
+    ///
+    /// [`eval_bytes`]: Self::eval_bytes
+    pub fn eval_file(&self, path: impl AsRef<std::path::Path>) -> Result<Value<'_>> {
+        let path = path.as_ref();
+        let data = std::fs::read(path)?;
+        self.eval_bytes(&data, &path.to_string_lossy())
+    }
+
+    /// Creates a JS string from UTF-8 text read from `reader`.
+    ///
+    /// The text is read in 64 KiB chunks, each copied into a JS string and
+    /// joined once at the end, so the Rust side never holds more than one
+    /// chunk. The JS heap briefly holds both the chunks and the joined
+    /// string, about twice the text's size, until the chunks are collected.
+    /// Invalid UTF-8 is an [`Error::RuntimeError`]; a read error comes back
+    /// as [`Error::Io`].
+    pub fn create_string_from_reader(&self, reader: impl std::io::Read) -> Result<JsString<'_>> {
+        string::from_reader(self, reader)
+    }
+
+    /// Checks that `code` parses, without running it.
+    ///
+    /// The C ABI has no entry that only prepares a script, so the source is
+    /// compiled as the body of `new Function(code)`, which cannot break out
+    /// of the body and never calls it. Unlike a script, this accepts a
+    /// top-level `return`. Requires `eval` support in the runtime. A syntax
+    /// error is returned as [`Error::JsError`], its message prefixed with
+    /// `url`.
+    pub fn check_syntax(&self, code: &str, url: &str) -> Result<()> {
+        let function = self.builtin("Function")?;
+        let code = JsString::new(self, code)?;
+        match function.call_as_constructor_internal(&[code.into()]) {
+            Ok(_) => Ok(()),
+            Err(Error::JsError {
+                name,
+                message,
+                stack,
+            }) => Err(Error::JsError {
+                name,
+                message: format!("{}: {}", url, message),
+                stack,
+            }),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Checks the syntax of each `(url, code)` pair up front and returns
+    /// them ready for [`evaluate_prepared`](Self::evaluate_prepared).
+    ///
+    /// Fails on the first script that does not parse, with its URL in the
+    /// message, as for [`check_syntax`](Self::check_syntax). The scripts
+    /// are not run.
+    pub fn preload_scripts(&self, scripts: &[(&str, &str)]) -> Result<Vec<PreparedJavaScript>> {
+        script::preload(self, scripts)
+    }
+
+    /// Evaluates a script returned by
+    /// [`preload_scripts`](Self::preload_scripts).
+    pub fn evaluate_prepared(&self, script: &PreparedJavaScript) -> Result<Value<'_>> {
+        script::evaluate(self, script)
+    }
+
+    /// Compiles `body` once as a function taking `params`, like
+    /// `new Function(...params, body)`, so it can be called many times
+    /// without re-parsing. The function runs in the global scope, not the
+    /// caller's. Requires `eval` support in the runtime.
+    pub fn prepare_callable(&self, params: &[&str], body: &str) -> Result<Function<'_>> {
+        let function = self.builtin("Function")?;
+        let mut args = params
+            .iter()
+            .map(|param| Ok(JsString::new(self, param)?.into()))
+            .collect::<Result<Vec<Value>>>()?;
+        args.push(JsString::new(self, body)?.into());
+        function
+            .call_as_constructor_internal(&args)?
+            .into_function()
+    }
+
+    /// The global object.
+    pub fn global(&self) -> Object<'_> {
+        (*self.cached_global()).clone()
+    }
+
+    /// Reads the global `name`, like `globalThis[name]`.
+    pub fn get_global(&self, name: &str) -> Result<Value<'_>> {
+        self.cached_global().get(name)
+    }
+
+    /// Writes the global `name`, like `globalThis[name] = value`.
+    pub fn set_global<'rt>(&'rt self, name: &str, value: Value<'rt>) -> Result<()> {
+        self.cached_global().set(name, value)
+    }
+
+    /// A view of the cached global object that must not be dropped.
+    fn cached_global(&self) -> ManuallyDrop<Object<'_>> {
+        let ptr = match self.state.global.get() {
+            Some(ptr) => ptr,
+            None => {
+                let global = unsafe { abi_call!(self, get_global_object) };
+                self.state.global.set(Some(global.pointer));
+                global.pointer
+            }
+        };
+        ManuallyDrop::new(unsafe { Object::from_raw(self, ptr) })
+    }
+
+    /// The engine's `HermesInternal` object, whose methods such as
+    /// `getInstrumentedStats` expose diagnostics otherwise only reachable
+    /// from JS. Fails if the runtime was created without it.
+    pub fn hermes_internal(&self) -> Result<Object<'_>> {
+        match self.global().get("HermesInternal")? {
+            Value::Object(internal) => Ok(internal),
+            _ => Err(Error::RuntimeError(
+                "HermesInternal is not enabled in this runtime".into(),
+            )),
+        }
+    }
+
+    /// Current heap statistics. Fails if the runtime was created without
+    /// `HermesInternal`.
+    pub fn heap_info(&self) -> Result<HeapInfo> {
+        heap::heap_info(self)
+    }
+
+    /// Cumulative garbage collection timings, for correlating latency
+    /// with GC activity. Fails if the runtime was created without
+    /// `HermesInternal`.
+    pub fn gc_stats(&self) -> Result<GcStats> {
+        heap::gc_stats(self)
+    }
+
+    /// Runs a full garbage collection.
+    pub fn collect_garbage(&self) -> Result<()> {
+        heap::collect_garbage(self)
+    }
+
+    /// Captures the global object's properties, so that
+    /// [`restore_global`](Self::restore_global) can undo later changes.
+    /// Lets one runtime be reused across tests with cheap isolation.
+    pub fn global_snapshot(&self) -> Result<GlobalSnapshot<'_>> {
+        snapshot::capture(self)
+    }
+
+    /// Restores the globals captured in `snapshot`: globals added since are
+    /// deleted, and changed or deleted ones get their old value back.
+    ///
+    /// Globals declared with top-level `var` or `function` cannot be deleted
+    /// and are set to `undefined` instead.
+    pub fn restore_global<'rt>(&'rt self, snapshot: &GlobalSnapshot<'rt>) -> Result<()> {
+        snapshot::restore(self, snapshot)
+    }
+
+    /// Defines the global `name` as a constant: scripts can read it but
+    /// not reassign, delete or enumerate it. Fails if `name` is already a
+    /// non-configurable global.
+    pub fn define_constant<'rt>(&'rt self, name: &str, value: Value<'rt>) -> Result<()> {
+        self.global().define_property(
+            name,
+            PropertyDescriptor {
+                value: Some(value),
+                writable: Some(false),
+                enumerable: Some(false),
+                configurable: Some(false),
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Runs queued microtasks until the queue is empty.
+    pub fn drain_microtasks(&self) -> Result<()> {
+        loop {
+            let res = unsafe { abi_call!(self, drain_microtasks, -1) };
+            if error::check_bool(self, res)? {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Queues `func` to be called with no arguments as a microtask, like
+    /// `queueMicrotask(func)`. Fails if the runtime has no `Promise`, which
+    /// queueing goes through.
+    pub fn queue_microtask<'rt>(&'rt self, func: &Function<'rt>) -> Result<()> {
+        promise::enqueue_job(self, func)
+    }
+
+    /// Queues `f` to run once as a microtask, like
+    /// [`queue_microtask`](Self::queue_microtask) with a Rust closure.
+    pub fn queue_rust_microtask(&self, f: impl FnOnce() + 'static) -> Result<()> {
+        let f = Cell::new(Some(f));
+        let job = Function::from_host_fn(self, "microtask", 0, move |_, _, _| {
+            if let Some(f) = f.take() {
+                f();
+            }
+            Ok(Value::Undefined)
+        })?;
+        promise::enqueue_job(self, &job)
+    }
+
+    /// Drains microtasks until `promise` settles, returning its value or,
+    /// if it was rejected, the reason as an [`Error::JsError`] or
+    /// [`Error::JsException`].
+    /// Fails if the promise is still pending once the queue is empty.
+    pub fn await_promise<'rt>(&'rt self, promise: &Promise<'rt>) -> Result<Value<'rt>> {
+        promise::await_promise(self, promise)
+    }
+
+    /// Exposes `stream` to `for await...of` as an async iterator: each
+    /// `next()` pulls one item, converts it with [`IntoJs`] and returns a
+    /// promise of `{ value, done }`. Items are pulled lazily, and the
+    /// object is its own iterator, so it can be consumed once.
+    pub fn create_async_iterable<I>(&self, stream: I) -> Result<Object<'_>>
+    where
+        I: IntoIterator,
+        I::IntoIter: 'static,
+        I::Item: for<'a> IntoJs<'a>,
+    {
+        async_iter::create_async_iterable(self, stream)
+    }
+
+    /// Evaluates `code` as a module and returns its exports.
+    ///
+    /// The C ABI has no ES module entry point, so this is a CommonJS
+    /// module: `import`/`export` syntax is rejected, and the code instead
+    /// assigns to `exports` or `module.exports`, e.g. `exports.default =
+    /// 42`. Top-level declarations stay local to the module. `require` is
+    /// the global one, such as installed by
+    /// [`install_require`](Self::install_require).
+    pub fn eval_module(&self, code: &str, url: &str) -> Result<Value<'_>> {
+        module::eval_module(self, code, url)
+    }
+
+    /// Installs a global `console` with `log`, `info`, `warn` and `error`.
+    /// Arguments are formatted like a browser's console and joined with
+    /// spaces; `log` and `info` print to stdout, `warn` and `error` to
+    /// stderr. Replaces any existing `console`.
+    pub fn install_console(&self) -> Result<()> {
+        console::install(self, console::print)
+    }
+
+    /// Like [`install_console`](Self::install_console), but hands each
+    /// formatted line to `sink` instead of printing it. `sink` is kept
+    /// alive by the `console` methods and dropped with them, at the latest
+    /// with the runtime.
+    pub fn install_console_with(
+        &self,
+        sink: impl FnMut(ConsoleLevel, String) + 'static,
+    ) -> Result<()> {
+        console::install(self, sink)
+    }
+
+    /// Defines a global CommonJS `require(id)` for running bundles.
+    ///
+    /// `resolver` returns the source of module `id`, which runs once with
+    /// `exports`, `require` and `module` in scope; its `module.exports` is
+    /// cached per id. Ids are passed through verbatim, so `"./a"` and `"a"`
+    /// are different modules. See [`ModuleRegistry`] for resolving ids
+    /// relative to the importing module.
+    pub fn install_require(
+        &self,
+        resolver: impl Fn(&str) -> Result<String> + 'static,
+    ) -> Result<()> {
+        module::install_require(self, resolver)
+    }
+
+    /// Raises a `kind` JS error with `message` from Rust code.
+    ///
+    /// The error becomes pending. Inside a host function it is thrown into
+    /// JS when the function returns, even if it returns `Ok`. Elsewhere the
+    /// next `eval*` or [`Function`] call returns it as [`Error::JsError`]
+    /// without running; other calls, like property reads, leave it pending.
+    /// A newer error replaces one still pending.
+    pub fn throw_error(&self, kind: JsErrorKind, message: &str) -> Result<()> {
+        let ctor = self.builtin(kind.name())?;
+        let error = ctor.call_as_constructor_internal(&[JsString::new(self, message)?.into()])?;
+        if let Some(old) = self.state.thrown.replace(Some(error.into_raw())) {
+            drop(unsafe { Value::from_raw(self, old) });
+        }
+        Ok(())
+    }
+
+    /// Whether an error raised with [`throw_error`](Self::throw_error) is
+    /// still pending.
+    ///
+    /// Errors raised by the engine are never left pending: the call that
+    /// raised them returns them and clears them.
+    pub fn has_pending_exception(&self) -> bool {
+        let thrown = self.state.thrown.take();
+        let pending = thrown.is_some();
+        self.state.thrown.set(thrown);
+        pending
+    }
+
+    /// Clears the pending error raised with
+    /// [`throw_error`](Self::throw_error) and returns it, so a host function
+    /// can recover instead of throwing it.
+    pub fn take_pending_exception(&self) -> Option<Error> {
+        error::take_thrown(self)
+    }
+
+    /// Probes which optional language features this runtime provides, by
+    /// evaluating a small test for each. Useful to check which config flags
+    /// a given Hermes build honours.
+    pub fn active_features(&self) -> RuntimeFeatures {
+        features::detect(self)
+    }
+
+    /// Number of host function calls currently active on this runtime.
+    ///
+    /// The engine does not expose its call stack, so this is a Rust-side
+    /// count of JS-to-Rust transitions: `0` outside any host function, `1`
+    /// inside one called from a script, and one more for each host function
+    /// re-entered from JS on top of that. JS-only frames are not counted.
+    /// Host functions can check it to bail out of runaway recursion.
+    pub fn js_stack_depth(&self) -> usize {
+        self.state.host_depth.get()
+    }
+
+    /// Caps [`js_stack_depth`](Self::js_stack_depth). A host function or
+    /// host object call that would go deeper throws
+    /// `Error::RuntimeError("host reentrancy limit exceeded")` into JS
+    /// without running. `None`, the default, removes the cap.
+    pub fn set_host_reentrancy_limit(&self, limit: Option<usize>) {
+        self.state.host_depth_limit.set(limit);
+    }
+
+    /// Creates a function named `name` backed by a Rust closure, without
+    /// installing it anywhere.
+    ///
+    /// Arguments are converted with [`FromJsArg`] and the result with
+    /// [`IntoJsRet`]. An `Err` returned by the closure is thrown as the JS
+    /// error matching the [`Error`] variant.
+    pub fn create_func<Args, F: IntoHostFunction<Args>>(
+        &self,
+        name: &str,
+        func: F,
+    ) -> Result<Function<'_>> {
+        function::from_closure(self, name, func, None)
+    }
+
+    /// Registers a Rust closure as the global function `name`, converting
+    /// like [`create_func`](Self::create_func).
+    pub fn set_func<Args, F: IntoHostFunction<Args>>(&self, name: &str, func: F) -> Result<()> {
+        let func = self.create_func(name, func)?;
+        self.global().set(name, Value::Object(func.into()))
+    }
+
+    /// Registers the op `O`, generated by [`hermes_op`], as a global
+    /// function named after it.
+    pub fn register_op<O: HermesOp>(&self) -> Result<()> {
+        let func = Function::from_op::<O>(self)?;
+        self.global().set(O::NAME, func.into())
+    }
+
+    /// Like [`set_func`](Self::set_func), but an `Err` returned by the closure
+    /// is always thrown as a `kind` error, e.g. a `RangeError`.
+    pub fn set_func_throwing<Args, F: IntoHostFunction<Args>>(
+        &self,
+        name: &str,
+        func: F,
+        kind: JsErrorKind,
+    ) -> Result<()> {
+        let func = function::from_closure(self, name, func, Some(kind))?;
+        self.global().set(name, Value::Object(func.into()))
+    }
+}
+
+impl Drop for Runtime {
+    fn drop(&mut self) {
+        if self.owned {
+            if let Some(raw) = self.state.thrown.take() {
+                drop(unsafe { Value::from_raw(self, raw) });
+            }
+            for (_, ptr) in self.state.builtins.borrow_mut().drain() {
+                unsafe { invalidate(ptr) };
+            }
+            if let Some(ptr) = self.state.global.take() {
+                unsafe { invalidate(ptr) };
+            }
+            unsafe { abi_call!(self, release) }
+            self.state.released.set(true);
+        }
+    }
+}
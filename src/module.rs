@@ -0,0 +1,136 @@
+use std::rc::Rc;
+
+use crate::{FromJs, Function, IntoJs, Object, Result, Runtime, Value};
+
+/// Supplies module source to a [`ModuleRegistry`].
+pub trait ModuleLoader: 'static {
+    /// Resolves `specifier` to the URL the module is cached under.
+    /// `referrer` is the URL of the importing module, or `None` for imports
+    /// made from Rust.
+    fn resolve(&self, specifier: &str, referrer: Option<&str>) -> Result<String>;
+
+    /// Returns the source of the module at `url`.
+    fn load(&self, url: &str) -> Result<String>;
+}
+
+/// Adapts a plain source lookup for [`Runtime::install_require`]: ids are
+/// used verbatim as URLs.
+struct IdLoader<F>(F);
+
+impl<F: Fn(&str) -> Result<String> + 'static> ModuleLoader for IdLoader<F> {
+    fn resolve(&self, specifier: &str, _referrer: Option<&str>) -> Result<String> {
+        Ok(specifier.to_string())
+    }
+
+    fn load(&self, url: &str) -> Result<String> {
+        (self.0)(url)
+    }
+}
+
+/// The module map, written in JS so cached exports stay on the JS heap.
+/// A module is cached before it runs, so circular imports see the
+/// partially filled exports, and uncached again if it throws.
+const REGISTRY_SOURCE: &str = r#"(function (resolve, compile) {
+  var cache = new Map();
+  function load(specifier, referrer) {
+    var url = resolve(specifier, referrer);
+    var cached = cache.get(url);
+    if (cached) return cached.exports;
+    var module = { exports: {} };
+    cache.set(url, module);
+    try {
+      var require = function (specifier) { return load(specifier, url); };
+      compile(url).call(module.exports, module.exports, require, module);
+    } catch (e) {
+      cache.delete(url);
+      throw e;
+    }
+    return module.exports;
+  }
+  return { load: load, cache: cache };
+})"#;
+
+/// CommonJS-style modules loaded through a [`ModuleLoader`].
+///
+/// Each module runs once with `exports`, `require` and `module` in scope;
+/// later imports of the same resolved URL return its cached
+/// `module.exports`. The C ABI has no ES module support, so `import` and
+/// `export` syntax is not available.
+pub struct ModuleRegistry<'rt> {
+    load: Function<'rt>,
+    cache: Object<'rt>,
+}
+
+impl<'rt> ModuleRegistry<'rt> {
+    pub fn new(rt: &'rt Runtime, loader: impl ModuleLoader) -> Result<Self> {
+        let loader: Rc<dyn ModuleLoader> = Rc::new(loader);
+
+        let resolver = loader.clone();
+        let resolve = Function::from_host_fn(rt, "resolve", 2, move |rt, _this, args| {
+            let specifier = String::from_js(args.first().unwrap_or(&Value::Undefined))?;
+            let referrer = Option::<String>::from_js(args.get(1).unwrap_or(&Value::Undefined))?;
+            resolver
+                .resolve(&specifier, referrer.as_deref())?
+                .into_js(rt)
+        })?;
+        let compile = Function::from_host_fn(rt, "compile", 1, move |rt, _this, args| {
+            let url = String::from_js(args.first().unwrap_or(&Value::Undefined))?;
+            let source = loader.load(&url)?;
+            rt.eval_internal(&wrap(&source), &url)
+        })?;
+
+        let factory = rt
+            .eval_internal(REGISTRY_SOURCE, "<module registry>")?
+            .into_function()?;
+        let registry = factory
+            .call_internal(&[resolve.into(), compile.into()])?
+            .into_object()?;
+        Ok(ModuleRegistry {
+            load: registry.get("load")?.into_function()?,
+            cache: registry.get("cache")?.into_object()?,
+        })
+    }
+
+    /// Imports `specifier`, running the module on first use, and returns
+    /// its `module.exports`.
+    pub fn import(&self, specifier: &str) -> Result<Value<'rt>> {
+        self.load.invoke().arg(specifier).call()
+    }
+
+    /// Whether a module is cached under the resolved `url`.
+    pub fn is_cached(&self, url: &str) -> Result<bool> {
+        let rt = self.load.rt;
+        let has = rt.builtin("Map.prototype.has")?;
+        let cached =
+            has.call_with_this_internal(&self.cache.clone().into(), &[url.into_js(rt)?])?;
+        Ok(cached.as_bool() == Some(true))
+    }
+}
+
+/// Wraps module source in a function taking `exports`, `require` and
+/// `module`.
+fn wrap(source: &str) -> String {
+    // Keep the wrapper on the first line so line numbers match.
+    format!("(function (exports, require, module) {{ {}\n}})", source)
+}
+
+/// Runs `code` as a one-off CommonJS module and returns its exports.
+pub(crate) fn eval_module<'rt>(rt: &'rt Runtime, code: &str, url: &str) -> Result<Value<'rt>> {
+    let factory = rt.eval_with_url(&wrap(code), url)?.into_function()?;
+    let exports = Value::from(Object::new(rt)?);
+    let module = Object::new(rt)?;
+    module.set("exports", exports.clone())?;
+    let require = rt.global().get("require")?;
+    factory
+        .call_with_this_internal(&exports, &[exports.clone(), require, module.clone().into()])?;
+    module.get("exports")
+}
+
+/// Installs a global `require` backed by a registry over `resolver`.
+pub(crate) fn install_require(
+    rt: &Runtime,
+    resolver: impl Fn(&str) -> Result<String> + 'static,
+) -> Result<()> {
+    let registry = ModuleRegistry::new(rt, IdLoader(resolver))?;
+    rt.global().set("require", registry.load.into())
+}
@@ -0,0 +1,483 @@
+use libhermesabi_sys::*;
+
+use crate::{
+    error, host_object, Array, ArrayBuffer, Error, FromJs, Function, HostObject, IntoJs, JsString,
+    PropNameId, Result, Runtime, Value,
+};
+
+/// Most pairs [`Object::set_many`] writes with one call into JS, keeping the
+/// argument count well within the engine's limits.
+const SET_MANY_BATCH: usize = 1024;
+
+managed_pointer!(
+    /// A JavaScript object.
+    Object,
+    HermesABIObject,
+    clone_object(HermesABIObject)
+);
+
+impl<'rt> Object<'rt> {
+    /// Creates an empty object, like `{}`.
+    pub fn new(rt: &'rt Runtime) -> Result<Self> {
+        let res = unsafe { abi_call!(rt, create_object) };
+        let ptr = error::check_pointer(rt, res.ptr_or_error)?;
+        Ok(unsafe { Object::from_raw(rt, ptr) })
+    }
+
+    /// Creates an object whose properties are served by `object`.
+    pub fn from_host_object<T: HostObject>(rt: &'rt Runtime, object: T) -> Result<Self> {
+        host_object::create_host_object(rt, Box::new(object))
+    }
+
+    /// Reads the property `key`, invoking getters.
+    pub fn get(&self, key: &str) -> Result<Value<'rt>> {
+        self.get_with_propnameid(&PropNameId::new(self.rt, key)?)
+    }
+
+    /// Reads the property `key` and converts it, e.g.
+    /// `let n: f64 = obj.get_as("x")?`. Conversion errors name the key, as
+    /// in `at .x: expected number, got string`.
+    pub fn get_as<T: FromJs<'rt>>(&self, key: &str) -> Result<T> {
+        T::from_js(&self.get(key)?).map_err(|err| err.at(&format!(".{}", key)))
+    }
+
+    pub fn get_with_propnameid(&self, key: &PropNameId<'rt>) -> Result<Value<'rt>> {
+        let res = unsafe {
+            abi_call!(
+                self.rt,
+                get_object_property_from_propnameid,
+                self.as_abi(),
+                key.as_abi()
+            )
+        };
+        error::check_value(self.rt, res)
+    }
+
+    /// Writes the property `key`, invoking setters.
+    pub fn set(&self, key: &str, value: Value<'rt>) -> Result<()> {
+        self.set_with_propnameid(&PropNameId::new(self.rt, key)?, value)
+    }
+
+    pub fn set_with_propnameid(&self, key: &PropNameId<'rt>, value: Value<'rt>) -> Result<()> {
+        let raw = value.as_raw();
+        let res = unsafe {
+            abi_call!(
+                self.rt,
+                set_object_property_from_propnameid,
+                self.as_abi(),
+                key.as_abi(),
+                &raw
+            )
+        };
+        error::check_void(self.rt, res)
+    }
+
+    /// Writes each `(key, value)` pair in order, invoking setters.
+    ///
+    /// The writes are batched: the keys of up to 1024 pairs
+    /// are sent to JS as one string, built in a buffer reused across
+    /// batches, and the values as arguments of a single call, instead of
+    /// creating a string and a property name per key as [`set`](Self::set)
+    /// does. Writing to a read-only property fails, as in strict mode.
+    ///
+    /// Not atomic: stops at the first failing write and returns its error,
+    /// leaving the earlier writes, including those of earlier batches, in
+    /// place.
+    pub fn set_many(&self, pairs: &[(&str, Value<'rt>)]) -> Result<()> {
+        let rt = self.rt;
+        let assign = rt.cached_function("<set many>", || {
+            rt.eval_internal(
+                "(function (o, keys) {
+                    'use strict';
+                    keys = JSON.parse(keys);
+                    for (var i = 0; i < keys.length; i++) o[keys[i]] = arguments[i + 2];
+                })",
+                "<set many>",
+            )?
+            .into_function()
+        })?;
+        let this = Value::Object(self.clone());
+        let mut keys = String::new();
+        let mut args = Vec::with_capacity(pairs.len().min(SET_MANY_BATCH) + 2);
+        for batch in pairs.chunks(SET_MANY_BATCH) {
+            keys.clear();
+            keys.push('[');
+            for (i, (key, _)) in batch.iter().enumerate() {
+                if i > 0 {
+                    keys.push(',');
+                }
+                push_json_string(&mut keys, key);
+            }
+            keys.push(']');
+            let keys = Value::String(JsString::new(rt, &keys)?);
+
+            args.clear();
+            args.push(this.as_raw());
+            args.push(keys.as_raw());
+            args.extend(batch.iter().map(|(_, value)| value.as_raw()));
+            assign.call_with_raw_args(&Value::Undefined, &args)?;
+        }
+        Ok(())
+    }
+
+    /// Copies the own enumerable properties of each source onto the
+    /// object, like `Object.assign(self, ...sources)`. Later sources win,
+    /// and getters on sources and setters on the object are invoked.
+    pub fn assign(&self, sources: &[&Object<'rt>]) -> Result<()> {
+        let mut args: Vec<Value<'rt>> = Vec::with_capacity(sources.len() + 1);
+        args.push(self.clone().into());
+        args.extend(sources.iter().map(|&source| Value::from(source.clone())));
+        self.rt.builtin("Object.assign")?.call_internal(&args)?;
+        Ok(())
+    }
+
+    /// Reads the element at `index`, invoking getters.
+    ///
+    /// The C ABI has no property access keyed by a number, nor a way to
+    /// make a property name from one, so the index still goes to the engine
+    /// as a string key. It is formatted on the stack instead of with
+    /// `to_string`, and used directly without creating a [`PropNameId`].
+    pub fn get_index(&self, index: u32) -> Result<Value<'rt>> {
+        let key = index_key(self.rt, index)?;
+        let res = unsafe {
+            abi_call!(
+                self.rt,
+                get_object_property_from_string,
+                self.as_abi(),
+                key.as_abi()
+            )
+        };
+        error::check_value(self.rt, res)
+    }
+
+    /// Writes the element at `index`, invoking setters, keyed as for
+    /// [`get_index`](Self::get_index). Fails if the property is read-only.
+    pub fn set_index(&self, index: u32, value: Value<'rt>) -> Result<()> {
+        let key = index_key(self.rt, index)?;
+        let raw = value.as_raw();
+        let res = unsafe {
+            abi_call!(
+                self.rt,
+                set_object_property_from_string,
+                self.as_abi(),
+                key.as_abi(),
+                &raw
+            )
+        };
+        error::check_void(self.rt, res)
+    }
+
+    /// Defines or redefines the own property `key`, like
+    /// `Object.defineProperty`. Fails if the descriptor mixes a value with
+    /// accessors or the property is non-configurable.
+    pub fn define_property(&self, key: &str, desc: PropertyDescriptor<'rt>) -> Result<()> {
+        let rt = self.rt;
+        let descriptor = Object::new(rt)?;
+        if let Some(value) = desc.value {
+            descriptor.set("value", value)?;
+        }
+        if let Some(get) = desc.get {
+            descriptor.set("get", get.into())?;
+        }
+        if let Some(set) = desc.set {
+            descriptor.set("set", set.into())?;
+        }
+        for (name, flag) in [
+            ("writable", desc.writable),
+            ("enumerable", desc.enumerable),
+            ("configurable", desc.configurable),
+        ] {
+            if let Some(flag) = flag {
+                descriptor.set(name, Value::Bool(flag))?;
+            }
+        }
+        let define_property = rt.builtin("Object.defineProperty")?;
+        define_property.call_internal(&[
+            self.clone().into(),
+            key.into_js(rt)?,
+            descriptor.into(),
+        ])?;
+        Ok(())
+    }
+
+    /// Reads the property named by `key`, converted to a property key as
+    /// in `obj[key]`: numbers index, symbols are used as is, and other
+    /// values are converted to strings.
+    pub fn get_with_value(&self, key: &Value<'rt>) -> Result<Value<'rt>> {
+        let get = self.rt.builtin("Reflect.get")?;
+        get.call_internal(&[self.clone().into(), key.clone()])
+    }
+
+    /// Writes the property named by `key`, converted as in
+    /// [`get_with_value`](Self::get_with_value). Fails if the property is
+    /// read-only.
+    pub fn set_with_value(&self, key: &Value<'rt>, value: Value<'rt>) -> Result<()> {
+        let set = self.rt.builtin("Reflect.set")?;
+        let done = set.call_internal(&[self.clone().into(), key.clone(), value])?;
+        if done.as_bool() != Some(true) {
+            return Err(Error::TypeError(format!(
+                "cannot assign to property `{}` of object",
+                key
+            )));
+        }
+        Ok(())
+    }
+
+    /// Whether `key` is present on the object or its prototype chain.
+    pub fn has(&self, key: &str) -> Result<bool> {
+        let key = PropNameId::new(self.rt, key)?;
+        let res = unsafe {
+            abi_call!(
+                self.rt,
+                has_object_property_from_propnameid,
+                self.as_abi(),
+                key.as_abi()
+            )
+        };
+        error::check_bool(self.rt, res)
+    }
+
+    /// Names of the object's enumerable string-keyed properties, including
+    /// inherited ones, like a `for...in` loop.
+    pub fn property_names(&self) -> Result<Array<'rt>> {
+        let res = unsafe { abi_call!(self.rt, get_object_property_names, self.as_abi()) };
+        let ptr = error::check_pointer(self.rt, res.ptr_or_error)?;
+        Ok(unsafe { Array::from_raw(self.rt, ptr) })
+    }
+
+    /// Own enumerable string keys, in `Object.keys` order.
+    pub fn keys(&self) -> Result<Vec<String>> {
+        let keys = self.rt.builtin("Object.keys")?;
+        keys.call_internal(&[self.clone().into()])?
+            .into_array()?
+            .to_vec()
+    }
+
+    /// Own enumerable string-keyed properties and their values, in
+    /// `Object.keys` order. Values are read one at a time, invoking getters.
+    pub fn entries(&self) -> Result<Vec<(String, Value<'rt>)>> {
+        self.keys()?
+            .into_iter()
+            .map(|key| {
+                let value = self.get(&key)?;
+                Ok((key, value))
+            })
+            .collect()
+    }
+
+    /// Calls the method `name` with the object as `this`.
+    pub fn call_method(&self, name: &str, args: &[Value<'rt>]) -> Result<Value<'rt>> {
+        let method = match self.get(name)? {
+            Value::Object(method) if method.is_function() => method.into_function_unchecked(),
+            other => {
+                return Err(Error::TypeError(format!(
+                    "`{}` is not a function, got {}",
+                    name,
+                    other.kind().name()
+                )))
+            }
+        };
+        method.call_with_this(&self.clone().into(), args)
+    }
+
+    /// Calls the method `name` and starts a chain of further method calls
+    /// on its result, as in `obj.foo().bar()`.
+    pub fn method(&self, name: &str, args: &[Value<'rt>]) -> Result<MethodChain<'rt>> {
+        let value = self.call_method(name, args)?;
+        Ok(MethodChain {
+            value,
+            path: format!("{}()", name),
+        })
+    }
+
+    /// Makes the object immutable, like `Object.freeze`. Later writes are
+    /// ignored, or throw in strict-mode JS.
+    pub fn freeze(&self) -> Result<()> {
+        self.rt
+            .builtin("Object.freeze")?
+            .call_internal(&[self.clone().into()])?;
+        Ok(())
+    }
+
+    /// Prevents adding or removing properties, like `Object.seal`. Existing
+    /// writable properties can still be assigned.
+    pub fn seal(&self) -> Result<()> {
+        self.rt
+            .builtin("Object.seal")?
+            .call_internal(&[self.clone().into()])?;
+        Ok(())
+    }
+
+    pub fn is_frozen(&self) -> Result<bool> {
+        let frozen = self.rt.builtin("Object.isFrozen")?;
+        Ok(frozen.call_internal(&[self.clone().into()])?.as_bool() == Some(true))
+    }
+
+    pub fn is_sealed(&self) -> Result<bool> {
+        let sealed = self.rt.builtin("Object.isSealed")?;
+        Ok(sealed.call_internal(&[self.clone().into()])?.as_bool() == Some(true))
+    }
+
+    /// Whether `constructor.prototype` is on the object's prototype chain,
+    /// like `instanceof`.
+    pub fn instance_of(&self, constructor: &Function<'rt>) -> Result<bool> {
+        let res = unsafe { abi_call!(self.rt, instance_of, self.as_abi(), constructor.as_abi()) };
+        error::check_bool(self.rt, res)
+    }
+
+    /// A number identifying the object, the same through every handle to
+    /// it and for as long as it lives. Assigned on first use and never
+    /// reused within the runtime.
+    pub fn unique_id(&self) -> Result<u64> {
+        // A `WeakMap` kept in JS, so ids neither keep objects alive nor
+        // depend on handle addresses.
+        let rt = self.rt;
+        let id_of = rt.cached_function("<identity>", || {
+            rt.eval_internal(
+                "(function () {
+                    var ids = new WeakMap(), next = 0;
+                    return function (o) {
+                        var id = ids.get(o);
+                        if (id === undefined) ids.set(o, id = ++next);
+                        return id;
+                    };
+                })()",
+                "<identity>",
+            )?
+            .into_function()
+        })?;
+        u64::from_js(&id_of.call_internal(&[self.clone().into()])?)
+    }
+
+    pub fn is_array(&self) -> bool {
+        unsafe { abi_call!(self.rt, object_is_array, self.as_abi()) }
+    }
+
+    pub fn is_array_buffer(&self) -> bool {
+        unsafe { abi_call!(self.rt, object_is_arraybuffer, self.as_abi()) }
+    }
+
+    pub fn is_function(&self) -> bool {
+        unsafe { abi_call!(self.rt, object_is_function, self.as_abi()) }
+    }
+
+    /// Converts to a [`Function`], checking that the object is callable.
+    pub fn into_function(self) -> Result<Function<'rt>> {
+        Value::Object(self).into_function()
+    }
+
+    /// Converts to an [`Array`], checking that the object is an array.
+    pub fn into_array(self) -> Result<Array<'rt>> {
+        Value::Object(self).into_array()
+    }
+
+    /// Converts to an [`ArrayBuffer`], checking the object is one.
+    pub fn into_array_buffer(self) -> Result<ArrayBuffer<'rt>> {
+        Value::Object(self).into_array_buffer()
+    }
+
+    pub(crate) fn into_array_unchecked(self) -> Array<'rt> {
+        let rt = self.rt;
+        unsafe { Array::from_raw(rt, self.into_raw()) }
+    }
+
+    pub(crate) fn into_function_unchecked(self) -> Function<'rt> {
+        let rt = self.rt;
+        unsafe { Function::from_raw(rt, self.into_raw()) }
+    }
+}
+
+impl<'rt> From<Function<'rt>> for Object<'rt> {
+    fn from(f: Function<'rt>) -> Self {
+        let rt = f.rt;
+        unsafe { Object::from_raw(rt, f.into_raw()) }
+    }
+}
+
+/// How [`Object::define_property`] defines a property. Fields left as
+/// `None` are omitted from the descriptor, so a new property gets
+/// `undefined` and `false` for them, while redefining keeps the current
+/// ones.
+#[derive(Debug, Clone, Default)]
+pub struct PropertyDescriptor<'rt> {
+    pub value: Option<Value<'rt>>,
+    /// Called with the object as `this` when the property is read.
+    pub get: Option<Function<'rt>>,
+    /// Called with the object as `this` and the new value when the
+    /// property is assigned.
+    pub set: Option<Function<'rt>>,
+    pub writable: Option<bool>,
+    /// Whether the property shows up in `Object.keys` and `for...in`.
+    pub enumerable: Option<bool>,
+    /// Whether the property can be deleted or redefined.
+    pub configurable: Option<bool>,
+}
+
+/// The result of a method call that further methods can be called on,
+/// created by [`Object::method`].
+pub struct MethodChain<'rt> {
+    value: Value<'rt>,
+    /// The calls so far, like `foo().bar()`, for error messages.
+    path: String,
+}
+
+impl<'rt> MethodChain<'rt> {
+    /// Calls the method `name` on the previous result, which must be an
+    /// object.
+    pub fn method(self, name: &str, args: &[Value<'rt>]) -> Result<Self> {
+        let object = match self.value {
+            Value::Object(object) => object,
+            other => {
+                return Err(Error::TypeError(format!(
+                    "`{}` returned {}, not an object",
+                    self.path,
+                    other.kind().name()
+                )))
+            }
+        };
+        let value = object.call_method(name, args).map_err(|err| match err {
+            Error::TypeError(msg) => Error::TypeError(format!("{}: {}", self.path, msg)),
+            other => other,
+        })?;
+        Ok(MethodChain {
+            value,
+            path: format!("{}.{}()", self.path, name),
+        })
+    }
+
+    /// The result of the last call.
+    pub fn value(self) -> Value<'rt> {
+        self.value
+    }
+}
+
+/// `index` in decimal as a JS string, formatted without allocating.
+fn index_key(rt: &Runtime, index: u32) -> Result<JsString<'_>> {
+    let mut buf = [0u8; 10];
+    let mut start = buf.len();
+    let mut n = index;
+    loop {
+        start -= 1;
+        buf[start] = b'0' + (n % 10) as u8;
+        n /= 10;
+        if n == 0 {
+            break;
+        }
+    }
+    // ASCII digits are valid UTF-8.
+    unsafe { JsString::from_utf8_unchecked(rt, &buf[start..]) }
+}
+
+/// Appends `s` to `buf` as a JSON string literal.
+fn push_json_string(buf: &mut String, s: &str) {
+    buf.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => buf.push_str("\\\""),
+            '\\' => buf.push_str("\\\\"),
+            c if (c as u32) < 0x20 => buf.push_str(&format!("\\u{:04x}", c as u32)),
+            c => buf.push(c),
+        }
+    }
+    buf.push('"');
+}
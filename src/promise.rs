@@ -0,0 +1,101 @@
+use libhermesabi_sys::*;
+
+use crate::{error, Error, FromJs, Function, JsString, Object, Result, Runtime, Value};
+
+managed_pointer!(
+    /// A JavaScript `Promise`. Use [`Runtime::await_promise`] to get its
+    /// result.
+    Promise,
+    HermesABIObject,
+    clone_object(HermesABIObject)
+);
+
+impl<'rt> Promise<'rt> {
+    /// A promise fulfilled with `value`, like `Promise.resolve(value)`.
+    /// A promise passed in is returned as is.
+    pub fn resolve(rt: &'rt Runtime, value: Value<'rt>) -> Result<Self> {
+        let ctor = Value::from(rt.builtin("Promise")?);
+        let resolve = rt.builtin("Promise.resolve")?;
+        resolve
+            .call_with_this_internal(&ctor, &[value])?
+            .into_promise()
+    }
+
+    /// Returns `Ok(None)` for values that are not promises.
+    pub(crate) fn from_value(value: &Value<'rt>) -> Result<Option<Self>> {
+        let Value::Object(object) = value else {
+            return Ok(None);
+        };
+        let rt = object.rt;
+        let to_string = rt.builtin("Object.prototype.toString")?;
+        let tag = String::from_js(&to_string.call_with_this_internal(value, &[])?)?;
+        if tag != "[object Promise]" {
+            return Ok(None);
+        }
+        Ok(Some(unsafe {
+            Promise::from_raw(rt, object.clone().into_raw())
+        }))
+    }
+
+    pub fn as_object(&self) -> Object<'rt> {
+        self.clone().into()
+    }
+}
+
+/// Queues `job` as a microtask by attaching it to an already fulfilled
+/// promise, as the C ABI has no entry for queueing jobs directly.
+pub(crate) fn enqueue_job<'rt>(rt: &'rt Runtime, job: &Function<'rt>) -> Result<()> {
+    let resolved = Promise::resolve(rt, Value::Undefined)?;
+    let then = rt.builtin("Promise.prototype.then")?;
+    then.call_with_this_internal(&resolved.into(), &[job.clone().into()])?;
+    Ok(())
+}
+
+/// Runs microtasks until `promise` settles.
+pub(crate) fn await_promise<'rt>(rt: &'rt Runtime, promise: &Promise<'rt>) -> Result<Value<'rt>> {
+    // `then` gets `Reflect.set` bound to a holder object, so the outcome is
+    // recorded without a host callback.
+    let holder = Object::new(rt)?;
+    let bind = rt.builtin("Function.prototype.bind")?;
+    let set = Value::from(rt.builtin("Reflect.set")?);
+    let recorder = |key: &str| -> Result<Value<'rt>> {
+        bind.call_with_this_internal(
+            &set,
+            &[
+                Value::Undefined,
+                holder.clone().into(),
+                JsString::new(rt, key)?.into(),
+            ],
+        )
+    };
+    let then = rt.builtin("Promise.prototype.then")?;
+    then.call_with_this_internal(
+        &promise.as_object().into(),
+        &[recorder("fulfilled")?, recorder("rejected")?],
+    )?;
+    rt.drain_microtasks()?;
+
+    if holder.has("fulfilled")? {
+        holder.get("fulfilled")
+    } else if holder.has("rejected")? {
+        let reason = holder.get("rejected")?;
+        Err(error::thrown_error(rt, &reason))
+    } else {
+        Err(Error::RuntimeError(
+            "promise is still pending after draining microtasks".into(),
+        ))
+    }
+}
+
+impl<'rt> From<Promise<'rt>> for Object<'rt> {
+    fn from(promise: Promise<'rt>) -> Self {
+        let rt = promise.rt;
+        unsafe { Object::from_raw(rt, promise.into_raw()) }
+    }
+}
+
+impl<'rt> From<Promise<'rt>> for Value<'rt> {
+    fn from(promise: Promise<'rt>) -> Self {
+        Value::Object(promise.into())
+    }
+}
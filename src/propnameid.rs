@@ -0,0 +1,54 @@
+use libhermesabi_sys::*;
+
+use crate::buffer::GrowableBuffer;
+use crate::{error, Error, JsString, Result, Runtime, Symbol};
+
+managed_pointer!(
+    /// An interned property name, used as a key for property access.
+    PropNameId,
+    HermesABIPropNameID,
+    clone_propnameid(HermesABIPropNameID)
+);
+
+impl<'rt> PropNameId<'rt> {
+    /// Creates a property name from UTF-8 text.
+    pub fn new(rt: &'rt Runtime, name: &str) -> Result<Self> {
+        Self::from_string(&JsString::new(rt, name)?)
+    }
+
+    /// Creates a property name from a JS string.
+    pub fn from_string(s: &JsString<'rt>) -> Result<Self> {
+        let rt = s.rt;
+        let res = unsafe { abi_call!(rt, create_propnameid_from_string, s.as_abi()) };
+        let ptr = error::check_pointer(rt, res.ptr_or_error)?;
+        Ok(unsafe { PropNameId::from_raw(rt, ptr) })
+    }
+
+    /// Creates a property name from a symbol, e.g. `Symbol.iterator`.
+    pub fn from_symbol(s: &Symbol<'rt>) -> Result<Self> {
+        let rt = s.rt;
+        let res = unsafe { abi_call!(rt, create_propnameid_from_symbol, s.as_abi()) };
+        let ptr = error::check_pointer(rt, res.ptr_or_error)?;
+        Ok(unsafe { PropNameId::from_raw(rt, ptr) })
+    }
+
+    /// Copies the name out as UTF-8.
+    pub fn to_rust_string(&self) -> Result<String> {
+        let mut buf = GrowableBuffer::new();
+        unsafe {
+            abi_call!(
+                self.rt,
+                get_utf8_from_propnameid,
+                self.as_abi(),
+                buf.as_abi()
+            )
+        };
+        String::from_utf8(buf.into_bytes()).map_err(|e| Error::RuntimeError(e.to_string()))
+    }
+}
+
+impl PartialEq for PropNameId<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        unsafe { abi_call!(self.rt, prop_name_id_equals, self.as_abi(), other.as_abi()) }
+    }
+}
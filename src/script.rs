@@ -0,0 +1,37 @@
+use crate::{Result, Runtime, Value};
+
+/// A script whose syntax has been checked, created by
+/// [`Runtime::preload_scripts`] and run with [`Runtime::evaluate_prepared`].
+///
+/// The C ABI cannot keep compiled code between calls, so this holds the
+/// checked source and the script is compiled again when evaluated. It is
+/// not tied to a runtime and can be evaluated in any of them.
+#[derive(Debug, Clone)]
+pub struct PreparedJavaScript {
+    url: String,
+    code: String,
+}
+
+impl PreparedJavaScript {
+    /// The URL the script reports in stack traces.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+pub(crate) fn preload(rt: &Runtime, scripts: &[(&str, &str)]) -> Result<Vec<PreparedJavaScript>> {
+    scripts
+        .iter()
+        .map(|&(url, code)| {
+            rt.check_syntax(code, url)?;
+            Ok(PreparedJavaScript {
+                url: url.to_string(),
+                code: code.to_string(),
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn evaluate<'rt>(rt: &'rt Runtime, script: &PreparedJavaScript) -> Result<Value<'rt>> {
+    rt.eval_with_url(&script.code, &script.url)
+}
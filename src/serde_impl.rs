@@ -0,0 +1,628 @@
+use std::fmt::Display;
+
+use serde::de::value::StringDeserializer;
+use serde::de::{self, DeserializeOwned, IntoDeserializer, Visitor};
+use serde::ser::{self, Serialize};
+
+use crate::convert::MAX_SAFE_INTEGER;
+use crate::{Array, ArrayBuffer, ArrayIter, Error, IntoJs, Object, Result, Runtime, Value};
+
+/// Converts `value` to JS through its `Serialize` impl.
+pub fn to_value<'rt, T: Serialize + ?Sized>(rt: &'rt Runtime, value: &T) -> Result<Value<'rt>> {
+    value.serialize(Serializer::new(rt))
+}
+
+/// Reads a `Deserialize` type from a JS value.
+pub fn from_value<'rt, T: DeserializeOwned>(value: &Value<'rt>) -> Result<T> {
+    T::deserialize(Deserializer::new(value.clone()))
+}
+
+impl ser::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Error::TypeError(msg.to_string())
+    }
+}
+
+impl de::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Error::TypeError(msg.to_string())
+    }
+}
+
+/// A serde `Serializer` producing JS values.
+///
+/// Structs and maps become objects, sequences and tuples arrays, and byte
+/// slices `ArrayBuffer`s. `None` and unit values are `null`. Enums use
+/// serde's default external tagging: unit variants are their name as a
+/// string, other variants a single-key object like `{ "Variant": payload }`.
+/// Integers outside the safe range become `BigInt`s, as with [`IntoJs`].
+#[derive(Clone, Copy)]
+pub struct Serializer<'rt> {
+    rt: &'rt Runtime,
+}
+
+impl<'rt> Serializer<'rt> {
+    pub fn new(rt: &'rt Runtime) -> Self {
+        Serializer { rt }
+    }
+}
+
+/// `{ [variant]: value }`, the externally tagged form of an enum variant.
+fn tagged<'rt>(rt: &'rt Runtime, variant: &str, value: Value<'rt>) -> Result<Value<'rt>> {
+    let object = Object::new(rt)?;
+    object.set(variant, value)?;
+    Ok(object.into())
+}
+
+impl<'rt> ser::Serializer for Serializer<'rt> {
+    type Ok = Value<'rt>;
+    type Error = Error;
+    type SerializeSeq = SerializeArray<'rt>;
+    type SerializeTuple = SerializeArray<'rt>;
+    type SerializeTupleStruct = SerializeArray<'rt>;
+    type SerializeTupleVariant = SerializeVariant<SerializeArray<'rt>>;
+    type SerializeMap = SerializeObject<'rt>;
+    type SerializeStruct = SerializeObject<'rt>;
+    type SerializeStructVariant = SerializeVariant<SerializeObject<'rt>>;
+
+    fn serialize_bool(self, v: bool) -> Result<Value<'rt>> {
+        Ok(Value::Bool(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Value<'rt>> {
+        Ok(Value::Number(v.into()))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Value<'rt>> {
+        Ok(Value::Number(v.into()))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Value<'rt>> {
+        Ok(Value::Number(v.into()))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Value<'rt>> {
+        v.into_js(self.rt)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Value<'rt>> {
+        Ok(Value::Number(v.into()))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Value<'rt>> {
+        Ok(Value::Number(v.into()))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Value<'rt>> {
+        Ok(Value::Number(v.into()))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Value<'rt>> {
+        v.into_js(self.rt)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Value<'rt>> {
+        Ok(Value::Number(v.into()))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Value<'rt>> {
+        Ok(Value::Number(v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Value<'rt>> {
+        v.into_js(self.rt)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Value<'rt>> {
+        v.into_js(self.rt)
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Value<'rt>> {
+        Ok(ArrayBuffer::from_slice(self.rt, v)?.into())
+    }
+
+    fn serialize_none(self) -> Result<Value<'rt>> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Value<'rt>> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Value<'rt>> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Value<'rt>> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<Value<'rt>> {
+        variant.into_js(self.rt)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Value<'rt>> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Value<'rt>> {
+        let value = value
+            .serialize(self)
+            .map_err(|err| err.at(&format!(".{}", variant)))?;
+        tagged(self.rt, variant, value)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SerializeArray<'rt>> {
+        Ok(SerializeArray {
+            rt: self.rt,
+            items: Vec::with_capacity(len.unwrap_or(0)),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SerializeArray<'rt>> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerializeArray<'rt>> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeVariant<SerializeArray<'rt>>> {
+        Ok(SerializeVariant {
+            variant,
+            inner: self.serialize_seq(Some(len))?,
+        })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<SerializeObject<'rt>> {
+        Ok(SerializeObject {
+            object: Object::new(self.rt)?,
+            key: None,
+        })
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<SerializeObject<'rt>> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeVariant<SerializeObject<'rt>>> {
+        Ok(SerializeVariant {
+            variant,
+            inner: self.serialize_map(Some(len))?,
+        })
+    }
+}
+
+/// Collects sequence and tuple elements into an array.
+pub struct SerializeArray<'rt> {
+    rt: &'rt Runtime,
+    items: Vec<Value<'rt>>,
+}
+
+impl<'rt> SerializeArray<'rt> {
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        let index = self.items.len();
+        let value = value
+            .serialize(Serializer::new(self.rt))
+            .map_err(|err| err.at(&format!("[{}]", index)))?;
+        self.items.push(value);
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Value<'rt>> {
+        let array = Array::new(self.rt, self.items.len())?;
+        for (index, item) in self.items.into_iter().enumerate() {
+            array.set(index, item)?;
+        }
+        Ok(array.into())
+    }
+}
+
+impl<'rt> ser::SerializeSeq for SerializeArray<'rt> {
+    type Ok = Value<'rt>;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value<'rt>> {
+        self.finish()
+    }
+}
+
+impl<'rt> ser::SerializeTuple for SerializeArray<'rt> {
+    type Ok = Value<'rt>;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value<'rt>> {
+        self.finish()
+    }
+}
+
+impl<'rt> ser::SerializeTupleStruct for SerializeArray<'rt> {
+    type Ok = Value<'rt>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value<'rt>> {
+        self.finish()
+    }
+}
+
+/// Collects map entries and struct fields into an object.
+pub struct SerializeObject<'rt> {
+    object: Object<'rt>,
+    /// The key passed to `serialize_key`, waiting for its value.
+    key: Option<String>,
+}
+
+impl<'rt> SerializeObject<'rt> {
+    fn insert<T: Serialize + ?Sized>(&mut self, key: &str, value: &T) -> Result<()> {
+        let value = value
+            .serialize(Serializer::new(self.object.rt))
+            .map_err(|err| err.at(&format!(".{}", key)))?;
+        self.object.set(key, value)
+    }
+}
+
+impl<'rt> ser::SerializeMap for SerializeObject<'rt> {
+    type Ok = Value<'rt>;
+    type Error = Error;
+
+    /// Keys must serialize to strings, numbers or booleans; the latter two
+    /// are stored under their JS string form, like `"1"`.
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<()> {
+        let key = match key.serialize(Serializer::new(self.object.rt))? {
+            Value::String(s) => s.to_rust_string()?,
+            key @ (Value::Number(_) | Value::Bool(_)) => key.to_json_string(None)?,
+            other => return Err(Error::expected("string or number map key", &other)),
+        };
+        self.key = Some(key);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        let key = self
+            .key
+            .take()
+            .expect("serialize_value called before serialize_key");
+        self.insert(&key, value)
+    }
+
+    fn end(self) -> Result<Value<'rt>> {
+        Ok(self.object.into())
+    }
+}
+
+impl<'rt> ser::SerializeStruct for SerializeObject<'rt> {
+    type Ok = Value<'rt>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        self.insert(key, value)
+    }
+
+    fn end(self) -> Result<Value<'rt>> {
+        Ok(self.object.into())
+    }
+}
+
+/// A tuple or struct variant, wrapped in `{ [variant]: payload }` when done.
+pub struct SerializeVariant<S> {
+    variant: &'static str,
+    inner: S,
+}
+
+impl<'rt> ser::SerializeTupleVariant for SerializeVariant<SerializeArray<'rt>> {
+    type Ok = Value<'rt>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.inner
+            .push(value)
+            .map_err(|err| err.at(&format!(".{}", self.variant)))
+    }
+
+    fn end(self) -> Result<Value<'rt>> {
+        let rt = self.inner.rt;
+        tagged(rt, self.variant, self.inner.finish()?)
+    }
+}
+
+impl<'rt> ser::SerializeStructVariant for SerializeVariant<SerializeObject<'rt>> {
+    type Ok = Value<'rt>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        self.inner
+            .insert(key, value)
+            .map_err(|err| err.at(&format!(".{}", self.variant)))
+    }
+
+    fn end(self) -> Result<Value<'rt>> {
+        let rt = self.inner.object.rt;
+        tagged(rt, self.variant, self.inner.object.into())
+    }
+}
+
+/// A serde `Deserializer` reading a JS value.
+///
+/// The mirror of [`Serializer`]: objects read as maps or structs, arrays as
+/// sequences, `ArrayBuffer`s as bytes, and `undefined` and `null` as `None`
+/// or unit. Properties holding `undefined` are skipped, so they count as
+/// missing fields. Numbers that are safe integers are offered to the
+/// visitor as integers, others as `f64`.
+pub struct Deserializer<'rt> {
+    value: Value<'rt>,
+}
+
+impl<'rt> Deserializer<'rt> {
+    pub fn new(value: Value<'rt>) -> Self {
+        Deserializer { value }
+    }
+}
+
+fn visit_number<'de, V: Visitor<'de>>(n: f64, visitor: V) -> Result<V::Value> {
+    if n.fract() == 0.0 && n.abs() <= MAX_SAFE_INTEGER {
+        if n >= 0.0 {
+            visitor.visit_u64(n as u64)
+        } else {
+            visitor.visit_i64(n as i64)
+        }
+    } else {
+        visitor.visit_f64(n)
+    }
+}
+
+impl<'de, 'rt> de::Deserializer<'de> for Deserializer<'rt> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.value {
+            Value::Undefined | Value::Null => visitor.visit_unit(),
+            Value::Bool(b) => visitor.visit_bool(b),
+            Value::Number(n) => visit_number(n, visitor),
+            Value::String(s) => visitor.visit_string(s.to_rust_string()?),
+            Value::BigInt(big) if big.is_i64() => visitor.visit_i64(big.truncate_to_i64()),
+            Value::BigInt(big) if big.is_u64() => visitor.visit_u64(big.truncate_to_u64()),
+            Value::BigInt(big) => visitor.visit_f64(big.to_f64_lossy()?),
+            Value::Object(o) if o.is_array() => {
+                let array = Value::from(o).into_array()?;
+                let len = array.len();
+                let mut seq = SeqDeserializer {
+                    items: array.iter(),
+                    index: 0,
+                };
+                let value = visitor.visit_seq(&mut seq)?;
+                match seq.items.len() {
+                    0 => Ok(value),
+                    remaining => Err(de::Error::invalid_length(
+                        len,
+                        &format!("{} elements", len - remaining).as_str(),
+                    )),
+                }
+            }
+            Value::Object(o) if o.is_array_buffer() => {
+                visitor.visit_byte_buf(Value::from(o).into_array_buffer()?.to_vec()?)
+            }
+            Value::Object(o) if o.is_function() => {
+                Err(Error::TypeError("cannot deserialize a function".into()))
+            }
+            Value::Object(o) => visitor.visit_map(MapDeserializer {
+                keys: o.keys()?.into_iter(),
+                object: o,
+                pending: None,
+            }),
+            other => Err(Error::TypeError(format!(
+                "cannot deserialize a {}",
+                other.kind().name()
+            ))),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.value {
+            Value::Undefined | Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        match self.value {
+            Value::String(s) => visitor.visit_enum(s.to_rust_string()?.into_deserializer()),
+            Value::Object(o) if !o.is_array() => {
+                let mut keys = o.keys()?;
+                if keys.len() != 1 {
+                    return Err(Error::TypeError(format!(
+                        "expected an object with a single key for enum {}, got {} keys",
+                        name,
+                        keys.len()
+                    )));
+                }
+                let variant = keys.remove(0);
+                let value = o.get(&variant)?;
+                let segment = format!(".{}", variant);
+                visitor
+                    .visit_enum(EnumDeserializer { variant, value })
+                    .map_err(|err| err.at(&segment))
+            }
+            other => Err(Error::expected("string or object", &other)),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+struct SeqDeserializer<'rt> {
+    items: ArrayIter<'rt>,
+    index: usize,
+}
+
+impl<'de, 'rt> de::SeqAccess<'de> for SeqDeserializer<'rt> {
+    type Error = Error;
+
+    fn next_element_seed<T: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>> {
+        let index = self.index;
+        self.index += 1;
+        match self.items.next() {
+            None => Ok(None),
+            Some(item) => seed
+                .deserialize(Deserializer::new(item?))
+                .map(Some)
+                .map_err(|err| err.at(&format!("[{}]", index))),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.items.len())
+    }
+}
+
+struct MapDeserializer<'rt> {
+    object: Object<'rt>,
+    keys: std::vec::IntoIter<String>,
+    /// The entry whose key was just handed out, waiting for its value.
+    pending: Option<(String, Value<'rt>)>,
+}
+
+impl<'de, 'rt> de::MapAccess<'de> for MapDeserializer<'rt> {
+    type Error = Error;
+
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        for key in self.keys.by_ref() {
+            let value = self.object.get(&key)?;
+            if value.is_undefined() {
+                continue;
+            }
+            let result = seed.deserialize(key.as_str().into_deserializer());
+            self.pending = Some((key, value));
+            return result.map(Some);
+        }
+        Ok(None)
+    }
+
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        let (key, value) = self
+            .pending
+            .take()
+            .expect("next_value_seed called before next_key_seed");
+        seed.deserialize(Deserializer::new(value))
+            .map_err(|err| err.at(&format!(".{}", key)))
+    }
+}
+
+struct EnumDeserializer<'rt> {
+    variant: String,
+    value: Value<'rt>,
+}
+
+impl<'de, 'rt> de::EnumAccess<'de> for EnumDeserializer<'rt> {
+    type Error = Error;
+    type Variant = VariantDeserializer<'rt>;
+
+    fn variant_seed<V: de::DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, VariantDeserializer<'rt>)> {
+        let variant = seed.deserialize(StringDeserializer::<Error>::new(self.variant))?;
+        Ok((variant, VariantDeserializer { value: self.value }))
+    }
+}
+
+struct VariantDeserializer<'rt> {
+    value: Value<'rt>,
+}
+
+impl<'de, 'rt> de::VariantAccess<'de> for VariantDeserializer<'rt> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<()> {
+        match self.value {
+            Value::Undefined | Value::Null => Ok(()),
+            other => Err(Error::expected("null", &other)),
+        }
+    }
+
+    fn newtype_variant_seed<T: de::DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value> {
+        seed.deserialize(Deserializer::new(self.value))
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value> {
+        de::Deserializer::deserialize_seq(Deserializer::new(self.value), visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        de::Deserializer::deserialize_map(Deserializer::new(self.value), visitor)
+    }
+}
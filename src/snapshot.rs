@@ -0,0 +1,59 @@
+use crate::{JsString, Object, Result, Runtime, Value};
+
+/// The global object's own string-keyed properties at one point in time,
+/// captured by [`Runtime::global_snapshot`].
+///
+/// The snapshot is shallow: it restores which value each global name holds,
+/// not changes made inside those values, such as a property added to
+/// `Array.prototype`. Symbol-keyed globals are ignored.
+#[derive(Debug, Clone)]
+pub struct GlobalSnapshot<'rt> {
+    entries: Vec<(String, Value<'rt>)>,
+}
+
+fn own_names(rt: &Runtime, global: &Object) -> Result<Vec<String>> {
+    let names = rt.builtin("Object.getOwnPropertyNames")?;
+    names
+        .call_internal(&[global.clone().into()])?
+        .into_array()?
+        .to_vec()
+}
+
+pub(crate) fn capture(rt: &Runtime) -> Result<GlobalSnapshot<'_>> {
+    let global = rt.global();
+    let entries = own_names(rt, &global)?
+        .into_iter()
+        .map(|name| {
+            let value = global.get(&name)?;
+            Ok((name, value))
+        })
+        .collect::<Result<_>>()?;
+    Ok(GlobalSnapshot { entries })
+}
+
+pub(crate) fn restore<'rt>(rt: &'rt Runtime, snapshot: &GlobalSnapshot<'rt>) -> Result<()> {
+    let global = rt.global();
+    let delete = rt.builtin("Reflect.deleteProperty")?;
+    let same_value = rt.builtin("Object.is")?;
+
+    for name in own_names(rt, &global)? {
+        if snapshot.entries.iter().any(|(known, _)| *known == name) {
+            continue;
+        }
+        let key = Value::String(JsString::new(rt, &name)?);
+        let deleted = delete.call_internal(&[global.clone().into(), key])?;
+        // Top-level `var` and function declarations cannot be deleted.
+        if deleted.as_bool() != Some(true) {
+            global.set(&name, Value::Undefined)?;
+        }
+    }
+
+    for (name, value) in &snapshot.entries {
+        let current = global.get(name)?;
+        let unchanged = same_value.call_internal(&[current, value.clone()])?;
+        if unchanged.as_bool() != Some(true) {
+            global.set(name, value.clone())?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,208 @@
+use std::cmp::Ordering;
+use std::io::Read;
+
+use libhermesabi_sys::*;
+
+use crate::buffer::GrowableBuffer;
+use crate::{error, Array, Error, FromJs, Result, Runtime, Value};
+
+managed_pointer!(
+    /// A JavaScript string.
+    JsString,
+    HermesABIString,
+    clone_string(HermesABIString)
+);
+
+impl<'rt> JsString<'rt> {
+    /// Creates a JS string from UTF-8 text.
+    pub fn new(rt: &'rt Runtime, s: &str) -> Result<Self> {
+        unsafe { Self::from_utf8_unchecked(rt, s.as_bytes()) }
+    }
+
+    /// Creates a JS string from bytes that must be UTF-8. Invalid UTF-8 is
+    /// an [`Error::TypeError`].
+    pub fn from_utf8(rt: &'rt Runtime, bytes: &[u8]) -> Result<Self> {
+        let s = std::str::from_utf8(bytes)
+            .map_err(|e| Error::TypeError(format!("invalid UTF-8: {}", e)))?;
+        Self::new(rt, s)
+    }
+
+    /// Creates a JS string from bytes without checking that they are UTF-8.
+    ///
+    /// # Safety
+    ///
+    /// `bytes` must be valid UTF-8, e.g. already checked by the caller. The
+    /// engine does not validate its input, so other bytes give a string
+    /// with unspecified contents.
+    pub unsafe fn from_utf8_unchecked(rt: &'rt Runtime, bytes: &[u8]) -> Result<Self> {
+        let res = abi_call!(rt, create_string_from_utf8, bytes.as_ptr(), bytes.len());
+        let ptr = error::check_pointer(rt, res.ptr_or_error)?;
+        Ok(JsString::from_raw(rt, ptr))
+    }
+
+    /// Copies the string out as UTF-8.
+    pub fn to_rust_string(&self) -> Result<String> {
+        String::from_utf8(self.utf8_bytes()).map_err(|e| Error::RuntimeError(e.to_string()))
+    }
+
+    /// Length in UTF-8 bytes.
+    ///
+    /// The ABI has no length query, so this still converts the string into a
+    /// scratch buffer, but skips validating and allocating a `String`.
+    pub fn len_utf8(&self) -> usize {
+        self.utf8_bytes().len()
+    }
+
+    /// Length in UTF-16 code units, i.e. the JS `length`. Characters outside
+    /// the BMP count as two.
+    ///
+    /// Read with one call into JS rather than by copying the string out;
+    /// if that call fails, the length is counted from a UTF-8 copy.
+    pub fn len_utf16(&self) -> usize {
+        self.js_length()
+            .unwrap_or_else(|_| utf16_len_of_utf8(&self.utf8_bytes()))
+    }
+
+    /// Whether the string is `""`, checked without copying it out.
+    pub fn is_empty(&self) -> bool {
+        self.len_utf16() == 0
+    }
+
+    fn js_length(&self) -> Result<usize> {
+        let rt = self.rt;
+        let length = rt.cached_function("<string length>", || {
+            rt.eval_internal("(function (s) { return s.length; })", "<string length>")?
+                .into_function()
+        })?;
+        usize::from_js(&length.call_internal(&[self.clone().into()])?)
+    }
+
+    /// The two strings joined, built in JS without copying either out.
+    pub fn concat(&self, other: &JsString<'rt>) -> Result<JsString<'rt>> {
+        let concat = self.rt.builtin("String.prototype.concat")?;
+        concat
+            .call_with_this_internal(&self.clone().into(), &[other.clone().into()])?
+            .into_string()
+    }
+
+    /// Orders the strings like JS `<`, by UTF-16 code units. This can differ
+    /// from comparing the Rust strings, which orders by code points.
+    pub fn compare(&self, other: &JsString<'rt>) -> Result<Ordering> {
+        let rt = self.rt;
+        let compare = rt.cached_function("<string compare>", || {
+            rt.eval_internal(
+                "(function (a, b) { return a < b ? -1 : a > b ? 1 : 0; })",
+                "<string compare>",
+            )?
+            .into_function()
+        })?;
+        let order = compare.call_internal(&[self.clone().into(), other.clone().into()])?;
+        Ok(order
+            .as_number()
+            .unwrap_or(0.0)
+            .partial_cmp(&0.0)
+            .unwrap_or(Ordering::Equal))
+    }
+
+    pub fn starts_with(&self, prefix: &JsString<'rt>) -> Result<bool> {
+        self.test("String.prototype.startsWith", prefix)
+    }
+
+    pub fn ends_with(&self, suffix: &JsString<'rt>) -> Result<bool> {
+        self.test("String.prototype.endsWith", suffix)
+    }
+
+    fn test(&self, method: &'static str, arg: &JsString<'rt>) -> Result<bool> {
+        let method = self.rt.builtin(method)?;
+        let result = method.call_with_this_internal(&self.clone().into(), &[arg.clone().into()])?;
+        Ok(result.as_bool() == Some(true))
+    }
+
+    fn utf8_bytes(&self) -> Vec<u8> {
+        let mut buf = GrowableBuffer::new();
+        unsafe { abi_call!(self.rt, get_utf8_from_string, self.as_abi(), buf.as_abi()) };
+        buf.into_bytes()
+    }
+}
+
+/// Builds a large JS string from pieces without holding the whole text on
+/// the Rust side.
+///
+/// Each piece becomes its own JS string; [`finish`](Self::finish) joins them
+/// once with `Array.prototype.join`.
+pub struct JsStringBuilder<'rt> {
+    pieces: Array<'rt>,
+}
+
+impl<'rt> JsStringBuilder<'rt> {
+    pub fn new(rt: &'rt Runtime) -> Result<Self> {
+        Ok(JsStringBuilder {
+            pieces: Array::new(rt, 0)?,
+        })
+    }
+
+    /// Appends `s`.
+    pub fn push_str(&mut self, s: &str) -> Result<()> {
+        if s.is_empty() {
+            return Ok(());
+        }
+        let piece = JsString::new(self.pieces.rt, s)?;
+        self.pieces.push(piece.into())
+    }
+
+    /// Joins the pieces into one string.
+    pub fn finish(self) -> Result<JsString<'rt>> {
+        let rt = self.pieces.rt;
+        let join = rt.builtin("Array.prototype.join")?;
+        let separator = JsString::new(rt, "")?;
+        join.call_with_this_internal(&self.pieces.into(), &[Value::String(separator)])?
+            .into_string()
+    }
+}
+
+/// UTF-16 code units needed for the UTF-8 text `bytes`. Each code point
+/// starts with a non-continuation byte; four-byte sequences are the ones
+/// encoded as surrogate pairs in UTF-16.
+fn utf16_len_of_utf8(bytes: &[u8]) -> usize {
+    bytes
+        .iter()
+        .map(|&b| match b {
+            0x80..=0xBF => 0,
+            0xF0..=0xFF => 2,
+            _ => 1,
+        })
+        .sum()
+}
+
+/// Size of the buffer [`Runtime::create_string_from_reader`] reads into.
+const READ_CHUNK: usize = 64 * 1024;
+
+pub(crate) fn from_reader<'rt>(rt: &'rt Runtime, mut reader: impl Read) -> Result<JsString<'rt>> {
+    let mut builder = JsStringBuilder::new(rt)?;
+    let mut buf = vec![0; READ_CHUNK];
+    // Bytes of a UTF-8 sequence split across two reads.
+    let mut pending = 0;
+    loop {
+        let read = match reader.read(&mut buf[pending..]) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.into()),
+        };
+        let filled = pending + read;
+        let valid = match std::str::from_utf8(&buf[..filled]) {
+            Ok(text) => text.len(),
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(e) => return Err(Error::RuntimeError(e.to_string())),
+        };
+        builder.push_str(std::str::from_utf8(&buf[..valid]).expect("validated above"))?;
+        buf.copy_within(valid..filled, 0);
+        pending = filled - valid;
+    }
+    if pending > 0 {
+        return Err(Error::RuntimeError(
+            "stream ended inside a UTF-8 sequence".into(),
+        ));
+    }
+    builder.finish()
+}
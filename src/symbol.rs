@@ -0,0 +1,97 @@
+use libhermesabi_sys::*;
+
+use crate::buffer::GrowableBuffer;
+use crate::{Error, IntoJs, Result, Runtime, Value};
+
+managed_pointer!(
+    /// A JavaScript symbol.
+    Symbol,
+    HermesABISymbol,
+    clone_symbol(HermesABISymbol)
+);
+
+/// The standard symbols stored on the global `Symbol`, like
+/// `Symbol.iterator`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WellKnown {
+    AsyncIterator,
+    HasInstance,
+    IsConcatSpreadable,
+    Iterator,
+    Match,
+    MatchAll,
+    Replace,
+    Search,
+    Species,
+    Split,
+    ToPrimitive,
+    ToStringTag,
+    Unscopables,
+}
+
+impl WellKnown {
+    /// The property of `Symbol` holding the symbol, e.g. `"iterator"`.
+    pub fn name(self) -> &'static str {
+        match self {
+            WellKnown::AsyncIterator => "asyncIterator",
+            WellKnown::HasInstance => "hasInstance",
+            WellKnown::IsConcatSpreadable => "isConcatSpreadable",
+            WellKnown::Iterator => "iterator",
+            WellKnown::Match => "match",
+            WellKnown::MatchAll => "matchAll",
+            WellKnown::Replace => "replace",
+            WellKnown::Search => "search",
+            WellKnown::Species => "species",
+            WellKnown::Split => "split",
+            WellKnown::ToPrimitive => "toPrimitive",
+            WellKnown::ToStringTag => "toStringTag",
+            WellKnown::Unscopables => "unscopables",
+        }
+    }
+}
+
+impl<'rt> Symbol<'rt> {
+    /// Creates a new unique symbol, like `Symbol(description)`.
+    pub fn new(rt: &'rt Runtime, description: Option<&str>) -> Result<Self> {
+        let symbol = rt.builtin("Symbol")?;
+        let args = match description {
+            Some(description) => vec![description.into_js(rt)?],
+            None => Vec::new(),
+        };
+        symbol.call_internal(&args)?.into_symbol()
+    }
+
+    /// The symbol registered under `key`, like `Symbol.for(key)`. The same
+    /// key always gives the same symbol.
+    pub fn for_key(rt: &'rt Runtime, key: &str) -> Result<Self> {
+        let symbol_for = rt.builtin("Symbol.for")?;
+        symbol_for.call_internal(&[key.into_js(rt)?])?.into_symbol()
+    }
+
+    /// A standard symbol, like `Symbol.iterator`.
+    pub fn well_known(rt: &'rt Runtime, symbol: WellKnown) -> Result<Self> {
+        let constructor = rt.global().get("Symbol")?.into_object()?;
+        constructor.get(symbol.name())?.into_symbol()
+    }
+
+    /// The description passed when the symbol was created, if any.
+    pub fn description(&self) -> Result<Option<String>> {
+        let rt = self.rt;
+        let describe = rt.cached_function("<symbol description>", || {
+            rt.eval_internal("(function (s) { return s.description; })", "<symbol>")?
+                .into_function()
+        })?;
+        match describe.call_internal(&[self.clone().into()])? {
+            Value::Undefined => Ok(None),
+            Value::String(description) => Ok(Some(description.to_rust_string()?)),
+            other => Err(Error::expected("string or undefined", &other)),
+        }
+    }
+
+    /// The symbol formatted as `Symbol(description)`.
+    pub fn to_rust_string(&self) -> Result<String> {
+        let mut buf = GrowableBuffer::new();
+        unsafe { abi_call!(self.rt, get_utf8_from_symbol, self.as_abi(), buf.as_abi()) };
+        String::from_utf8(buf.into_bytes()).map_err(|e| Error::RuntimeError(e.to_string()))
+    }
+}
@@ -0,0 +1,454 @@
+use std::marker::PhantomData;
+
+use crate::{ArrayBuffer, Error, FromJs, Object, Result, Runtime, Value};
+
+/// Element type of a JS typed array.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TypedArrayKind {
+    Int8,
+    Uint8,
+    Uint8Clamped,
+    Int16,
+    Uint16,
+    Int32,
+    Uint32,
+    Float32,
+    Float64,
+    BigInt64,
+    BigUint64,
+}
+
+impl TypedArrayKind {
+    const ALL: [TypedArrayKind; 11] = [
+        TypedArrayKind::Int8,
+        TypedArrayKind::Uint8,
+        TypedArrayKind::Uint8Clamped,
+        TypedArrayKind::Int16,
+        TypedArrayKind::Uint16,
+        TypedArrayKind::Int32,
+        TypedArrayKind::Uint32,
+        TypedArrayKind::Float32,
+        TypedArrayKind::Float64,
+        TypedArrayKind::BigInt64,
+        TypedArrayKind::BigUint64,
+    ];
+
+    /// Name of the JS constructor, e.g. `"Float64Array"`.
+    pub fn name(self) -> &'static str {
+        match self {
+            TypedArrayKind::Int8 => "Int8Array",
+            TypedArrayKind::Uint8 => "Uint8Array",
+            TypedArrayKind::Uint8Clamped => "Uint8ClampedArray",
+            TypedArrayKind::Int16 => "Int16Array",
+            TypedArrayKind::Uint16 => "Uint16Array",
+            TypedArrayKind::Int32 => "Int32Array",
+            TypedArrayKind::Uint32 => "Uint32Array",
+            TypedArrayKind::Float32 => "Float32Array",
+            TypedArrayKind::Float64 => "Float64Array",
+            TypedArrayKind::BigInt64 => "BigInt64Array",
+            TypedArrayKind::BigUint64 => "BigUint64Array",
+        }
+    }
+
+    /// Size of one element in bytes.
+    pub fn element_size(self) -> usize {
+        match self {
+            TypedArrayKind::Int8 | TypedArrayKind::Uint8 | TypedArrayKind::Uint8Clamped => 1,
+            TypedArrayKind::Int16 | TypedArrayKind::Uint16 => 2,
+            TypedArrayKind::Int32 | TypedArrayKind::Uint32 | TypedArrayKind::Float32 => 4,
+            TypedArrayKind::Float64 | TypedArrayKind::BigInt64 | TypedArrayKind::BigUint64 => 8,
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.name() == name)
+    }
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// A Rust type that typed array elements can be read as.
+///
+/// Implemented for the primitive matching each [`TypedArrayKind`]; `u8`
+/// also reads `Uint8ClampedArray`s.
+pub trait TypedArrayElement: Copy + sealed::Sealed {
+    const KIND: TypedArrayKind;
+
+    #[doc(hidden)]
+    fn accepts(kind: TypedArrayKind) -> bool {
+        kind == Self::KIND
+    }
+}
+
+macro_rules! impl_typed_array_element {
+    ($($ty:ty => $kind:ident),*) => {$(
+        impl sealed::Sealed for $ty {}
+
+        impl TypedArrayElement for $ty {
+            const KIND: TypedArrayKind = TypedArrayKind::$kind;
+        }
+    )*};
+}
+
+impl_typed_array_element!(
+    i8 => Int8,
+    i16 => Int16,
+    u16 => Uint16,
+    i32 => Int32,
+    u32 => Uint32,
+    f32 => Float32,
+    f64 => Float64,
+    i64 => BigInt64,
+    u64 => BigUint64
+);
+
+impl sealed::Sealed for u8 {}
+
+impl TypedArrayElement for u8 {
+    const KIND: TypedArrayKind = TypedArrayKind::Uint8;
+
+    fn accepts(kind: TypedArrayKind) -> bool {
+        matches!(kind, TypedArrayKind::Uint8 | TypedArrayKind::Uint8Clamped)
+    }
+}
+
+/// A read-only view of a JS typed array of any element type, created by
+/// [`Value::as_typed_array`].
+///
+/// [`to_vec`](Self::to_vec) copies the elements out. The slice accessors
+/// borrow the engine's backing store, shared with JS and other views, and
+/// are `unsafe`.
+#[derive(Debug, Clone)]
+pub struct TypedArrayRef<'rt> {
+    array: Object<'rt>,
+    buffer: ArrayBuffer<'rt>,
+    kind: TypedArrayKind,
+    byte_offset: usize,
+    len: usize,
+}
+
+impl<'rt> TypedArrayRef<'rt> {
+    /// Returns `Ok(None)` for values that are not typed arrays.
+    pub(crate) fn from_value(value: &Value<'rt>) -> Result<Option<Self>> {
+        let Value::Object(array) = value else {
+            return Ok(None);
+        };
+        let rt = array.rt;
+        let is_view = rt
+            .builtin("ArrayBuffer.isView")?
+            .call_internal(std::slice::from_ref(value))?;
+        if is_view.as_bool() != Some(true) {
+            return Ok(None);
+        }
+        // `[object Float64Array]`; `DataView`s have no matching kind.
+        let to_string = rt.builtin("Object.prototype.toString")?;
+        let tag = String::from_js(&to_string.call_with_this_internal(value, &[])?)?;
+        let Some(kind) = tag
+            .strip_prefix("[object ")
+            .and_then(|rest| rest.strip_suffix(']'))
+            .and_then(TypedArrayKind::from_name)
+        else {
+            return Ok(None);
+        };
+
+        let Ok(buffer) = array.get("buffer")?.into_array_buffer() else {
+            return Ok(None);
+        };
+        Ok(Some(TypedArrayRef {
+            array: array.clone(),
+            buffer,
+            kind,
+            byte_offset: read_usize(array, "byteOffset")?,
+            len: read_usize(array, "length")?,
+        }))
+    }
+
+    pub fn kind(&self) -> TypedArrayKind {
+        self.kind
+    }
+
+    /// Number of elements.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The typed array object.
+    pub fn as_object(&self) -> &Object<'rt> {
+        &self.array
+    }
+
+    /// The elements as raw bytes, in native byte order.
+    ///
+    /// Fails if the view no longer fits its buffer, e.g. because the
+    /// buffer was detached.
+    ///
+    /// # Safety
+    ///
+    /// While the slice is alive, the buffer must not be written through any
+    /// other handle, view or slice, and no JS may run: JS can write to the
+    /// buffer or detach it.
+    pub unsafe fn as_bytes(&self) -> Result<&[u8]> {
+        let byte_len = self.len * self.kind.element_size();
+        let data = view_data(&self.buffer, self.kind, self.byte_offset, byte_len, 1)?;
+        Ok(std::slice::from_raw_parts(data, byte_len))
+    }
+
+    /// The elements as `T`, which must match the array's kind: reading a
+    /// `Float32Array` as `f64` is an [`Error::TypeError`], not a
+    /// reinterpretation of its bytes.
+    ///
+    /// # Safety
+    ///
+    /// As for [`as_bytes`](Self::as_bytes).
+    pub unsafe fn as_slice<T: TypedArrayElement>(&self) -> Result<&[T]> {
+        check_kind::<T>(self.kind)?;
+        let data = view_data(
+            &self.buffer,
+            self.kind,
+            self.byte_offset,
+            self.len * self.kind.element_size(),
+            std::mem::align_of::<T>(),
+        )?;
+        Ok(std::slice::from_raw_parts(data.cast(), self.len))
+    }
+
+    /// Copies the elements out as `T`, which must match the array's kind.
+    pub fn to_vec<T: TypedArrayElement>(&self) -> Result<Vec<T>> {
+        // The slice is dropped before anything else can run.
+        Ok(unsafe { self.as_slice::<T>() }?.to_vec())
+    }
+}
+
+/// A JS typed array whose elements are `T`, e.g. a [`Float64Array`].
+///
+/// The backing store is shared with JS and with other views of the same
+/// buffer, so the safe accessors copy elements in and out. The slice
+/// accessors borrow the store directly and are `unsafe`.
+pub struct TypedArray<'rt, T: TypedArrayElement> {
+    array: Object<'rt>,
+    kind: TypedArrayKind,
+    _element: PhantomData<T>,
+}
+
+pub type Int8Array<'rt> = TypedArray<'rt, i8>;
+pub type Uint8Array<'rt> = TypedArray<'rt, u8>;
+pub type Int16Array<'rt> = TypedArray<'rt, i16>;
+pub type Uint16Array<'rt> = TypedArray<'rt, u16>;
+pub type Int32Array<'rt> = TypedArray<'rt, i32>;
+pub type Uint32Array<'rt> = TypedArray<'rt, u32>;
+pub type Float32Array<'rt> = TypedArray<'rt, f32>;
+pub type Float64Array<'rt> = TypedArray<'rt, f64>;
+pub type BigInt64Array<'rt> = TypedArray<'rt, i64>;
+pub type BigUint64Array<'rt> = TypedArray<'rt, u64>;
+
+impl<'rt, T: TypedArrayElement> TypedArray<'rt, T> {
+    /// Creates a zero-filled array of `len` elements, like
+    /// `new Float64Array(len)`.
+    pub fn new(rt: &'rt Runtime, len: usize) -> Result<Self> {
+        let ctor = rt.builtin(T::KIND.name())?;
+        let array = ctor
+            .call_as_constructor_internal(&[Value::Number(len as f64)])?
+            .into_object()?;
+        Ok(TypedArray {
+            array,
+            kind: T::KIND,
+            _element: PhantomData,
+        })
+    }
+
+    pub(crate) fn from_value(value: Value<'rt>) -> Result<Self> {
+        let Some(view) = TypedArrayRef::from_value(&value)? else {
+            return Err(Error::expected("typed array", &value));
+        };
+        check_kind::<T>(view.kind)?;
+        Ok(TypedArray {
+            array: view.array,
+            kind: view.kind,
+            _element: PhantomData,
+        })
+    }
+
+    pub fn kind(&self) -> TypedArrayKind {
+        self.kind
+    }
+
+    /// Number of elements, the JS `length`.
+    pub fn len(&self) -> Result<usize> {
+        read_usize(&self.array, "length")
+    }
+
+    pub fn is_empty(&self) -> Result<bool> {
+        Ok(self.len()? == 0)
+    }
+
+    /// Offset of the first element in [`buffer`](Self::buffer), in bytes.
+    pub fn byte_offset(&self) -> Result<usize> {
+        read_usize(&self.array, "byteOffset")
+    }
+
+    /// The backing `ArrayBuffer`.
+    pub fn buffer(&self) -> Result<ArrayBuffer<'rt>> {
+        self.array.get("buffer")?.into_array_buffer()
+    }
+
+    pub fn as_object(&self) -> &Object<'rt> {
+        &self.array
+    }
+
+    /// The elements, borrowed from the backing store.
+    ///
+    /// # Safety
+    ///
+    /// While the slice is alive, the buffer must not be written through any
+    /// other handle, view or slice, and no JS may run: JS can write to the
+    /// buffer or detach it. Prefer [`to_vec`](Self::to_vec).
+    pub unsafe fn as_slice(&self) -> Result<&[T]> {
+        let (data, len) = self.elements()?;
+        Ok(std::slice::from_raw_parts(data, len))
+    }
+
+    /// The elements, mutably borrowed from the backing store.
+    ///
+    /// # Safety
+    ///
+    /// While the slice is alive, the buffer must not be read or written
+    /// through any other handle, view or slice, and no JS may run. Prefer
+    /// [`copy_from_slice`](Self::copy_from_slice).
+    pub unsafe fn as_mut_slice(&mut self) -> Result<&mut [T]> {
+        let (data, len) = self.elements()?;
+        Ok(std::slice::from_raw_parts_mut(data, len))
+    }
+
+    /// Copies the elements out.
+    pub fn to_vec(&self) -> Result<Vec<T>> {
+        let (data, len) = self.elements()?;
+        let mut elements = Vec::with_capacity(len);
+        // The store is only read through the raw pointer, and no JS runs
+        // during the copy.
+        unsafe {
+            std::ptr::copy_nonoverlapping(data, elements.as_mut_ptr(), len);
+            elements.set_len(len);
+        }
+        Ok(elements)
+    }
+
+    /// Overwrites the elements with `src`, which must have the array's
+    /// length.
+    pub fn copy_from_slice(&mut self, src: &[T]) -> Result<()> {
+        let (data, len) = self.elements()?;
+        if len != src.len() {
+            return Err(Error::TypeError(format!(
+                "expected {} elements, got {}",
+                len,
+                src.len()
+            )));
+        }
+        unsafe { std::ptr::copy_nonoverlapping(src.as_ptr(), data, len) };
+        Ok(())
+    }
+
+    /// Copies as many elements as fit into `dst` and returns how many were
+    /// copied.
+    pub fn copy_to_slice(&self, dst: &mut [T]) -> Result<usize> {
+        let (data, len) = self.elements()?;
+        let n = len.min(dst.len());
+        unsafe { std::ptr::copy_nonoverlapping(data, dst.as_mut_ptr(), n) };
+        Ok(n)
+    }
+
+    fn elements(&self) -> Result<(*mut T, usize)> {
+        let len = self.len()?;
+        let data = view_data(
+            &self.buffer()?,
+            self.kind,
+            self.byte_offset()?,
+            len * self.kind.element_size(),
+            std::mem::align_of::<T>(),
+        )?;
+        Ok((data.cast(), len))
+    }
+}
+
+impl<T: TypedArrayElement> Clone for TypedArray<'_, T> {
+    fn clone(&self) -> Self {
+        TypedArray {
+            array: self.array.clone(),
+            kind: self.kind,
+            _element: PhantomData,
+        }
+    }
+}
+
+impl<T: TypedArrayElement> std::fmt::Debug for TypedArray<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple(self.kind.name())
+            .field(&self.array.ptr)
+            .finish()
+    }
+}
+
+impl<'rt, T: TypedArrayElement> From<TypedArray<'rt, T>> for Object<'rt> {
+    fn from(array: TypedArray<'rt, T>) -> Self {
+        array.array
+    }
+}
+
+impl<'rt, T: TypedArrayElement> From<TypedArray<'rt, T>> for Value<'rt> {
+    fn from(array: TypedArray<'rt, T>) -> Self {
+        Value::Object(array.array)
+    }
+}
+
+fn check_kind<T: TypedArrayElement>(kind: TypedArrayKind) -> Result<()> {
+    if T::accepts(kind) {
+        return Ok(());
+    }
+    Err(Error::TypeError(format!(
+        "expected {}, got {}",
+        T::KIND.name(),
+        kind.name()
+    )))
+}
+
+fn read_usize(object: &Object, key: &str) -> Result<usize> {
+    let value = object.get(key)?;
+    value
+        .as_number()
+        .map(|n| n as usize)
+        .ok_or_else(|| Error::expected("number", &value))
+}
+
+/// Start of the `byte_len` bytes at `byte_offset` in `buffer`, checked to
+/// lie within the buffer and to be aligned to `align`. Dangling but aligned
+/// when `byte_len` is zero.
+fn view_data(
+    buffer: &ArrayBuffer,
+    kind: TypedArrayKind,
+    byte_offset: usize,
+    byte_len: usize,
+    align: usize,
+) -> Result<*mut u8> {
+    if byte_offset + byte_len > buffer.len()? {
+        return Err(Error::RuntimeError(format!(
+            "{} view no longer fits its buffer",
+            kind.name()
+        )));
+    }
+    if byte_len == 0 {
+        return Ok(align as *mut u8);
+    }
+    let data = unsafe { buffer.data_ptr()?.add(byte_offset) };
+    if data.align_offset(align) != 0 {
+        return Err(Error::RuntimeError(format!(
+            "{} data is not aligned for its element type",
+            kind.name()
+        )));
+    }
+    Ok(data)
+}
@@ -0,0 +1,676 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use libhermesabi_sys::*;
+
+use crate::{
+    error, Array, ArrayBuffer, BigInt, Error, Function, JsMap, JsSet, JsString, Object, Promise,
+    Result, Runtime, Symbol, TypedArray, TypedArrayElement, TypedArrayRef,
+};
+
+/// A JavaScript value.
+///
+/// Primitives are stored inline; strings, symbols, BigInts and objects hold a
+/// handle owned by the runtime `'rt`.
+#[derive(Debug, Clone)]
+pub enum Value<'rt> {
+    Undefined,
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(JsString<'rt>),
+    Symbol(Symbol<'rt>),
+    BigInt(BigInt<'rt>),
+    Object(Object<'rt>),
+}
+
+/// The type of a [`Value`], without its payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ValueKind {
+    Undefined,
+    Null,
+    Boolean,
+    Number,
+    String,
+    Symbol,
+    BigInt,
+    Object,
+}
+
+impl ValueKind {
+    /// Lowercase name used in error messages, e.g. `"number"`.
+    pub fn name(self) -> &'static str {
+        match self {
+            ValueKind::Undefined => "undefined",
+            ValueKind::Null => "null",
+            ValueKind::Boolean => "boolean",
+            ValueKind::Number => "number",
+            ValueKind::String => "string",
+            ValueKind::Symbol => "symbol",
+            ValueKind::BigInt => "bigint",
+            ValueKind::Object => "object",
+        }
+    }
+}
+
+impl<'rt> Value<'rt> {
+    pub fn undefined() -> Self {
+        Value::Undefined
+    }
+
+    pub fn null() -> Self {
+        Value::Null
+    }
+
+    pub fn from_bool(b: bool) -> Self {
+        Value::Bool(b)
+    }
+
+    pub fn from_number(n: f64) -> Self {
+        Value::Number(n)
+    }
+
+    pub fn kind(&self) -> ValueKind {
+        match self {
+            Value::Undefined => ValueKind::Undefined,
+            Value::Null => ValueKind::Null,
+            Value::Bool(_) => ValueKind::Boolean,
+            Value::Number(_) => ValueKind::Number,
+            Value::String(_) => ValueKind::String,
+            Value::Symbol(_) => ValueKind::Symbol,
+            Value::BigInt(_) => ValueKind::BigInt,
+            Value::Object(_) => ValueKind::Object,
+        }
+    }
+
+    /// The result of JS `typeof`. Unlike [`ValueKind::name`], `null` is
+    /// `"object"` and callable objects are `"function"`.
+    pub fn type_of(&self) -> &'static str {
+        match self {
+            Value::Null => "object",
+            Value::Object(o) if o.is_function() => "function",
+            other => other.kind().name(),
+        }
+    }
+
+    pub fn is_undefined(&self) -> bool {
+        matches!(self, Value::Undefined)
+    }
+
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+
+    pub fn is_bool(&self) -> bool {
+        matches!(self, Value::Bool(_))
+    }
+
+    pub fn is_number(&self) -> bool {
+        matches!(self, Value::Number(_))
+    }
+
+    pub fn is_string(&self) -> bool {
+        matches!(self, Value::String(_))
+    }
+
+    pub fn is_symbol(&self) -> bool {
+        matches!(self, Value::Symbol(_))
+    }
+
+    pub fn is_bigint(&self) -> bool {
+        matches!(self, Value::BigInt(_))
+    }
+
+    pub fn is_object(&self) -> bool {
+        matches!(self, Value::Object(_))
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub fn as_number(&self) -> Option<f64> {
+        match self {
+            Value::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    pub fn into_string(self) -> Result<JsString<'rt>> {
+        match self {
+            Value::String(s) => Ok(s),
+            other => Err(Error::expected("string", &other)),
+        }
+    }
+
+    pub fn into_symbol(self) -> Result<Symbol<'rt>> {
+        match self {
+            Value::Symbol(s) => Ok(s),
+            other => Err(Error::expected("symbol", &other)),
+        }
+    }
+
+    pub fn into_bigint(self) -> Result<BigInt<'rt>> {
+        match self {
+            Value::BigInt(b) => Ok(b),
+            other => Err(Error::expected("bigint", &other)),
+        }
+    }
+
+    pub fn into_object(self) -> Result<Object<'rt>> {
+        match self {
+            Value::Object(o) => Ok(o),
+            other => Err(Error::expected("object", &other)),
+        }
+    }
+
+    /// Converts to an [`Array`] if the value is an array object.
+    pub fn into_array(self) -> Result<Array<'rt>> {
+        match self {
+            Value::Object(o) if o.is_array() => Ok(o.into_array_unchecked()),
+            other => Err(Error::expected("array", &other)),
+        }
+    }
+
+    /// Converts to an [`ArrayBuffer`] if the value is an `ArrayBuffer`.
+    pub fn into_array_buffer(self) -> Result<ArrayBuffer<'rt>> {
+        match self {
+            Value::Object(o) if o.is_array_buffer() => {
+                let rt = o.rt;
+                Ok(unsafe { ArrayBuffer::from_raw(rt, o.into_raw()) })
+            }
+            other => Err(Error::expected("ArrayBuffer", &other)),
+        }
+    }
+
+    /// Like `instanceof`: `false` for primitives, and an error if
+    /// `constructor` is not a function.
+    pub fn instance_of(&self, constructor: &Value<'rt>) -> Result<bool> {
+        let constructor = constructor.clone().into_function()?;
+        match self {
+            Value::Object(object) => object.instance_of(&constructor),
+            _ => Ok(false),
+        }
+    }
+
+    /// Like `===`: values of different kinds are unequal, `NaN` equals
+    /// nothing, `0` equals `-0`, and handles compare by identity for
+    /// objects and by content for strings and bigints. Never runs JS.
+    pub fn strict_equals(&self, other: &Value<'rt>) -> bool {
+        match (self, other) {
+            (Value::Undefined, Value::Undefined) | (Value::Null, Value::Null) => true,
+            (Value::Bool(a), Value::Bool(b)) => a == b,
+            (Value::Number(a), Value::Number(b)) => a == b,
+            (Value::String(a), Value::String(b)) => unsafe {
+                abi_call!(a.rt, strict_equals_string, a.as_abi(), b.as_abi())
+            },
+            (Value::Symbol(a), Value::Symbol(b)) => unsafe {
+                abi_call!(a.rt, strict_equals_symbol, a.as_abi(), b.as_abi())
+            },
+            (Value::BigInt(a), Value::BigInt(b)) => unsafe {
+                abi_call!(a.rt, strict_equals_bigint, a.as_abi(), b.as_abi())
+            },
+            (Value::Object(a), Value::Object(b)) => unsafe {
+                abi_call!(a.rt, strict_equals_object, a.as_abi(), b.as_abi())
+            },
+            _ => false,
+        }
+    }
+
+    /// Like `Object.is`: as [`strict_equals`](Self::strict_equals), except
+    /// that `NaN` equals `NaN` and `0` does not equal `-0`.
+    pub fn same_value(&self, other: &Value<'rt>) -> bool {
+        match (self, other) {
+            (Value::Number(a), Value::Number(b)) => {
+                (a.is_nan() && b.is_nan())
+                    || (a == b && a.is_sign_negative() == b.is_sign_negative())
+            }
+            _ => self.strict_equals(other),
+        }
+    }
+
+    /// The equality `Map` keys and `Set` members use: like
+    /// [`same_value`](Self::same_value), but `0` equals `-0`.
+    pub fn same_value_zero(&self, other: &Value<'rt>) -> bool {
+        match (self, other) {
+            (Value::Number(a), Value::Number(b)) => (a.is_nan() && b.is_nan()) || a == b,
+            _ => self.strict_equals(other),
+        }
+    }
+
+    /// Like `==`: as [`strict_equals`](Self::strict_equals), except that
+    /// `null` equals `undefined` and values of different kinds are
+    /// converted first, e.g. `"1" == 1`. Converting an object calls its
+    /// `valueOf` or `toString`, which can throw.
+    pub fn loose_equals(&self, other: &Value<'rt>) -> Result<bool> {
+        let Some(rt) = self.runtime().or_else(|| other.runtime()) else {
+            return Ok(match (self, other) {
+                (Value::Undefined | Value::Null, Value::Undefined | Value::Null) => true,
+                (Value::Bool(a), Value::Number(b)) | (Value::Number(b), Value::Bool(a)) => {
+                    (*a as u8 as f64) == *b
+                }
+                _ => self.strict_equals(other),
+            });
+        };
+        let equals = rt.cached_function("<loose equals>", || {
+            rt.eval_internal("(function (a, b) { return a == b; })", "<equals>")?
+                .into_function()
+        })?;
+        Ok(equals
+            .call_internal(&[self.clone(), other.clone()])?
+            .as_bool()
+            == Some(true))
+    }
+
+    /// Structural equality. Arrays are equal if their elements are, other
+    /// objects if they have the same own enumerable string keys, in any
+    /// order, with deeply equal values. Prototypes are not compared, but an
+    /// array never equals a non-array. Primitives and functions compare
+    /// with [`same_value_zero`](Self::same_value_zero).
+    ///
+    /// A pair of objects reached again while it is being compared, as in
+    /// cyclic structures, counts as equal. Reading properties invokes
+    /// getters, which can throw.
+    pub fn deep_equal(&self, other: &Value<'rt>) -> Result<bool> {
+        deep_equal(self, other, &mut Vec::new())
+    }
+
+    /// Converts to a [`Promise`] if the value is a promise.
+    pub fn into_promise(self) -> Result<Promise<'rt>> {
+        match Promise::from_value(&self)? {
+            Some(promise) => Ok(promise),
+            None => Err(Error::expected("Promise", &self)),
+        }
+    }
+
+    /// Converts to a [`JsMap`] if the value is a `Map` instance.
+    pub fn into_map(self) -> Result<JsMap<'rt>> {
+        match JsMap::from_value(&self)? {
+            Some(map) => Ok(map),
+            None => Err(Error::expected("Map", &self)),
+        }
+    }
+
+    /// Converts to a [`JsSet`] if the value is a `Set` instance.
+    pub fn into_set(self) -> Result<JsSet<'rt>> {
+        match JsSet::from_value(&self)? {
+            Some(set) => Ok(set),
+            None => Err(Error::expected("Set", &self)),
+        }
+    }
+
+    /// Converts to a [`TypedArray`] with elements of type `T`. Typed arrays
+    /// of another element type are an [`Error::TypeError`].
+    pub fn into_typed_array<T: TypedArrayElement>(self) -> Result<TypedArray<'rt, T>> {
+        TypedArray::from_value(self)
+    }
+
+    /// Converts to a [`Function`] if the value is a callable object.
+    pub fn into_function(self) -> Result<Function<'rt>> {
+        match self {
+            Value::Object(o) if o.is_function() => Ok(o.into_function_unchecked()),
+            other => Err(Error::expected("function", &other)),
+        }
+    }
+
+    /// Serializes the value with `JSON.stringify`, pretty-printed with
+    /// `indent` spaces per level if given.
+    ///
+    /// Values JSON cannot represent, such as `undefined` or functions, are an
+    /// [`Error::TypeError`]; exceptions thrown by `JSON.stringify`, e.g. for
+    /// circular structures, are returned as [`Error::JsError`].
+    pub fn to_json_string(&self, indent: Option<usize>) -> Result<String> {
+        let Some(rt) = self.runtime() else {
+            return match self {
+                Value::Null => Ok("null".into()),
+                Value::Bool(b) => Ok(b.to_string()),
+                Value::Number(n) if n.is_finite() => Ok(format_number(*n)),
+                Value::Number(_) => Ok("null".into()),
+                _ => Err(Error::TypeError(
+                    "undefined has no JSON representation".into(),
+                )),
+            };
+        };
+        let stringify = rt.builtin("JSON.stringify")?;
+        let indent = indent.map_or(Value::Undefined, |n| Value::Number(n as f64));
+        match stringify.call_internal(&[self.clone(), Value::Undefined, indent])? {
+            Value::String(json) => json.to_rust_string(),
+            _ => Err(Error::TypeError(format!(
+                "{} has no JSON representation",
+                self.type_of()
+            ))),
+        }
+    }
+
+    /// The runtime owning the value's handle; `None` for inline primitives.
+    pub(crate) fn runtime(&self) -> Option<&'rt Runtime> {
+        match self {
+            Value::String(s) => Some(s.rt),
+            Value::Symbol(s) => Some(s.rt),
+            Value::BigInt(b) => Some(b.rt),
+            Value::Object(o) => Some(o.rt),
+            _ => None,
+        }
+    }
+
+    /// A short label for tracing a value through logs: the literal for
+    /// primitives, like `42` or `"abc"`, and the kind plus a number for
+    /// objects, like `Object#3`. The number is assigned on first use and
+    /// stays the same for the object's lifetime, whichever handle it is
+    /// read through.
+    pub fn identity_hint(&self) -> String {
+        match self {
+            Value::String(s) => match s.to_rust_string() {
+                Ok(s) => format!("{:?}", s),
+                Err(_) => "String#?".into(),
+            },
+            Value::Symbol(s) => s.to_rust_string().unwrap_or_else(|_| "Symbol#?".into()),
+            Value::BigInt(b) => match b.to_js_string(10).and_then(|s| s.to_rust_string()) {
+                Ok(digits) => format!("{}n", digits),
+                Err(_) => "BigInt#?".into(),
+            },
+            Value::Object(o) => {
+                let kind = if o.is_function() {
+                    "Function"
+                } else if o.is_array() {
+                    "Array"
+                } else {
+                    "Object"
+                };
+                match o.unique_id() {
+                    Ok(id) => format!("{}#{}", kind, id),
+                    Err(_) => format!("{}#?", kind),
+                }
+            }
+            primitive => primitive.to_string(),
+        }
+    }
+
+    /// A view of the value's elements if it is a typed array such as a
+    /// `Float64Array`. `DataView`s and other objects return `None`.
+    pub fn as_typed_array(&self) -> Option<TypedArrayRef<'rt>> {
+        TypedArrayRef::from_value(self).ok().flatten()
+    }
+
+    /// Takes ownership of a raw ABI value created by `rt`.
+    #[allow(non_upper_case_globals)]
+    pub(crate) unsafe fn from_raw(rt: &'rt Runtime, raw: HermesABIValue) -> Self {
+        match raw.kind {
+            HermesABIValueKind_HermesABIValueKindNull => Value::Null,
+            HermesABIValueKind_HermesABIValueKindBoolean => Value::Bool(raw.data.boolean),
+            HermesABIValueKind_HermesABIValueKindNumber => Value::Number(raw.data.number),
+            HermesABIValueKind_HermesABIValueKindString => {
+                Value::String(JsString::from_raw(rt, raw.data.pointer))
+            }
+            HermesABIValueKind_HermesABIValueKindObject => {
+                Value::Object(Object::from_raw(rt, raw.data.pointer))
+            }
+            HermesABIValueKind_HermesABIValueKindSymbol => {
+                Value::Symbol(Symbol::from_raw(rt, raw.data.pointer))
+            }
+            HermesABIValueKind_HermesABIValueKindBigInt => {
+                Value::BigInt(BigInt::from_raw(rt, raw.data.pointer))
+            }
+            _ => Value::Undefined,
+        }
+    }
+
+    /// Clones a raw ABI value borrowed from the engine, e.g. a host function
+    /// argument.
+    #[allow(non_upper_case_globals)]
+    pub(crate) unsafe fn from_raw_borrowed(rt: &'rt Runtime, raw: &HermesABIValue) -> Self {
+        match raw.kind {
+            HermesABIValueKind_HermesABIValueKindString => {
+                let s = abi_call!(
+                    rt,
+                    clone_string,
+                    HermesABIString {
+                        pointer: raw.data.pointer
+                    }
+                );
+                Value::String(JsString::from_raw(rt, s.pointer))
+            }
+            HermesABIValueKind_HermesABIValueKindObject => {
+                let o = abi_call!(
+                    rt,
+                    clone_object,
+                    HermesABIObject {
+                        pointer: raw.data.pointer
+                    }
+                );
+                Value::Object(Object::from_raw(rt, o.pointer))
+            }
+            HermesABIValueKind_HermesABIValueKindSymbol => {
+                let s = abi_call!(
+                    rt,
+                    clone_symbol,
+                    HermesABISymbol {
+                        pointer: raw.data.pointer
+                    }
+                );
+                Value::Symbol(Symbol::from_raw(rt, s.pointer))
+            }
+            HermesABIValueKind_HermesABIValueKindBigInt => {
+                let b = abi_call!(
+                    rt,
+                    clone_bigint,
+                    HermesABIBigInt {
+                        pointer: raw.data.pointer
+                    }
+                );
+                Value::BigInt(BigInt::from_raw(rt, b.pointer))
+            }
+            _ => Value::from_raw(rt, *raw),
+        }
+    }
+
+    /// A raw view of the value. Pointer payloads remain owned by `self`.
+    pub(crate) fn as_raw(&self) -> HermesABIValue {
+        let (kind, data) = match self {
+            Value::Undefined => (
+                HermesABIValueKind_HermesABIValueKindUndefined,
+                HermesABIValue__bindgen_ty_1 { number: 0.0 },
+            ),
+            Value::Null => (
+                HermesABIValueKind_HermesABIValueKindNull,
+                HermesABIValue__bindgen_ty_1 { number: 0.0 },
+            ),
+            Value::Bool(b) => (
+                HermesABIValueKind_HermesABIValueKindBoolean,
+                HermesABIValue__bindgen_ty_1 { boolean: *b },
+            ),
+            Value::Number(n) => (
+                HermesABIValueKind_HermesABIValueKindNumber,
+                HermesABIValue__bindgen_ty_1 { number: *n },
+            ),
+            Value::String(s) => (
+                HermesABIValueKind_HermesABIValueKindString,
+                HermesABIValue__bindgen_ty_1 { pointer: s.ptr },
+            ),
+            Value::Symbol(s) => (
+                HermesABIValueKind_HermesABIValueKindSymbol,
+                HermesABIValue__bindgen_ty_1 { pointer: s.ptr },
+            ),
+            Value::BigInt(b) => (
+                HermesABIValueKind_HermesABIValueKindBigInt,
+                HermesABIValue__bindgen_ty_1 { pointer: b.ptr },
+            ),
+            Value::Object(o) => (
+                HermesABIValueKind_HermesABIValueKindObject,
+                HermesABIValue__bindgen_ty_1 { pointer: o.ptr },
+            ),
+        };
+        HermesABIValue { kind, data }
+    }
+
+    /// Transfers ownership of the value to the engine.
+    pub(crate) fn into_raw(self) -> HermesABIValue {
+        let raw = self.as_raw();
+        std::mem::forget(self);
+        raw
+    }
+}
+
+/// Formats like JS `String(value)`. Values that fail to convert, e.g. objects
+/// whose `toString` throws, print a placeholder.
+impl fmt::Display for Value<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self, self.runtime()) {
+            (Value::Undefined, _) => f.write_str("undefined"),
+            (Value::Null, _) => f.write_str("null"),
+            (Value::Bool(b), _) => write!(f, "{}", b),
+            (Value::Number(n), _) => f.write_str(&format_number(*n)),
+            (_, Some(rt)) => f.write_str(&error::extract_error_message(rt, self)),
+            (_, None) => unreachable!("handle values always have a runtime"),
+        }
+    }
+}
+
+/// [`Value::deep_equal`], with `seen` holding the pairs of objects being
+/// compared further up.
+fn deep_equal<'rt>(
+    a: &Value<'rt>,
+    b: &Value<'rt>,
+    seen: &mut Vec<(Object<'rt>, Object<'rt>)>,
+) -> Result<bool> {
+    let (Value::Object(x), Value::Object(y)) = (a, b) else {
+        return Ok(a.same_value_zero(b));
+    };
+    if a.strict_equals(b) {
+        return Ok(true);
+    }
+    if x.is_function() || y.is_function() || x.is_array() != y.is_array() {
+        return Ok(false);
+    }
+    let identical = |p: &Object<'rt>, q: &Object<'rt>| unsafe {
+        abi_call!(p.rt, strict_equals_object, p.as_abi(), q.as_abi())
+    };
+    if seen.iter().any(|(p, q)| identical(p, x) && identical(q, y)) {
+        return Ok(true);
+    }
+    seen.push((x.clone(), y.clone()));
+    let equal = deep_equal_contents(x, y, seen);
+    seen.pop();
+    equal
+}
+
+fn deep_equal_contents<'rt>(
+    x: &Object<'rt>,
+    y: &Object<'rt>,
+    seen: &mut Vec<(Object<'rt>, Object<'rt>)>,
+) -> Result<bool> {
+    if x.is_array() {
+        let x = Value::from(x.clone()).into_array()?;
+        let y = Value::from(y.clone()).into_array()?;
+        if x.len() != y.len() {
+            return Ok(false);
+        }
+        for index in 0..x.len() {
+            if !deep_equal(&x.get(index)?, &y.get(index)?, seen)? {
+                return Ok(false);
+            }
+        }
+        return Ok(true);
+    }
+    let keys = x.keys()?;
+    let other_keys: HashSet<String> = y.keys()?.into_iter().collect();
+    if keys.len() != other_keys.len() || !keys.iter().all(|key| other_keys.contains(key)) {
+        return Ok(false);
+    }
+    for key in &keys {
+        if !deep_equal(&x.get(key)?, &y.get(key)?, seen)? {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Formats a number like JS `Number.prototype.toString()`.
+fn format_number(n: f64) -> String {
+    if n.is_nan() {
+        return "NaN".into();
+    }
+    if n.is_infinite() {
+        return if n > 0.0 { "Infinity" } else { "-Infinity" }.into();
+    }
+    if n == 0.0 {
+        return "0".into();
+    }
+    // Shortest round-trip digits, e.g. "1.2345e-7".
+    let sci = format!("{:e}", n.abs());
+    let (mantissa, exp) = sci.split_once('e').expect("`{:e}` has an exponent");
+    let digits: String = mantissa.chars().filter(|c| *c != '.').collect();
+    let k = digits.len() as i32;
+    let point = exp.parse::<i32>().expect("valid exponent") + 1;
+
+    let body = if k <= point && point <= 21 {
+        format!("{}{}", digits, "0".repeat((point - k) as usize))
+    } else if 0 < point && point <= 21 {
+        format!(
+            "{}.{}",
+            &digits[..point as usize],
+            &digits[point as usize..]
+        )
+    } else if -6 < point && point <= 0 {
+        format!("0.{}{}", "0".repeat(-point as usize), digits)
+    } else {
+        let exp = point - 1;
+        let sign = if exp < 0 { '-' } else { '+' };
+        let (first, rest) = digits.split_at(1);
+        if rest.is_empty() {
+            format!("{}e{}{}", first, sign, exp.abs())
+        } else {
+            format!("{}.{}e{}{}", first, rest, sign, exp.abs())
+        }
+    };
+    if n < 0.0 {
+        format!("-{}", body)
+    } else {
+        body
+    }
+}
+
+impl<'rt> From<JsString<'rt>> for Value<'rt> {
+    fn from(s: JsString<'rt>) -> Self {
+        Value::String(s)
+    }
+}
+
+impl<'rt> From<Symbol<'rt>> for Value<'rt> {
+    fn from(s: Symbol<'rt>) -> Self {
+        Value::Symbol(s)
+    }
+}
+
+impl<'rt> From<BigInt<'rt>> for Value<'rt> {
+    fn from(b: BigInt<'rt>) -> Self {
+        Value::BigInt(b)
+    }
+}
+
+impl<'rt> From<Object<'rt>> for Value<'rt> {
+    fn from(o: Object<'rt>) -> Self {
+        Value::Object(o)
+    }
+}
+
+impl<'rt> From<Function<'rt>> for Value<'rt> {
+    fn from(f: Function<'rt>) -> Self {
+        Value::Object(f.into())
+    }
+}
+
+impl From<bool> for Value<'_> {
+    fn from(b: bool) -> Self {
+        Value::Bool(b)
+    }
+}
+
+impl From<f64> for Value<'_> {
+    fn from(n: f64) -> Self {
+        Value::Number(n)
+    }
+}
@@ -0,0 +1,60 @@
+use std::rc::Rc;
+
+use libhermesabi_sys::*;
+
+use crate::{error, Error, Function, Result, Runtime, RuntimeState, Value};
+
+/// A weak reference to a JS function, for host closures that need to call
+/// back into JS.
+///
+/// Host function closures are owned by the engine and freed only when their
+/// function object is collected. A closure holding its callback strongly,
+/// while the callback (or something it reaches) holds the host function,
+/// forms a cycle the collector cannot see through, and both leak. A
+/// `WeakFunction` does not keep the callback alive: the closure locks it
+/// with the runtime it is called with, and gets `None` once the callback
+/// has been collected.
+///
+/// Unlike [`Function`], it does not borrow the runtime, so `'static`
+/// closures can capture it.
+pub struct WeakFunction {
+    ptr: *mut HermesABIManagedPointer,
+    state: Rc<RuntimeState>,
+}
+
+impl WeakFunction {
+    pub(crate) fn new(func: &Function) -> Result<Self> {
+        let object = HermesABIObject { pointer: func.ptr };
+        let res = unsafe { abi_call!(func.rt, create_weak_object, object) };
+        let ptr = error::check_pointer(func.rt, res.ptr_or_error)?;
+        Ok(WeakFunction {
+            ptr,
+            state: func.rt.state.clone(),
+        })
+    }
+
+    /// The function, if it has not been collected. Fails if `rt` is not the
+    /// runtime the function belongs to.
+    pub fn lock<'rt>(&self, rt: &'rt Runtime) -> Result<Option<Function<'rt>>> {
+        if !Rc::ptr_eq(&self.state, &rt.state) {
+            return Err(Error::RuntimeError(
+                "weak function locked with a different runtime".into(),
+            ));
+        }
+        let weak = HermesABIWeakObject { pointer: self.ptr };
+        let raw = unsafe { abi_call!(rt, lock_weak_object, weak) };
+        match unsafe { Value::from_raw(rt, raw) } {
+            Value::Object(object) => Ok(Some(object.into_function_unchecked())),
+            _ => Ok(None),
+        }
+    }
+}
+
+impl Drop for WeakFunction {
+    fn drop(&mut self) {
+        // After the runtime is released the pointer has gone with it.
+        if !self.state.released.get() {
+            unsafe { crate::invalidate(self.ptr) }
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use rusty_hermes::{Array, Runtime, Value};
+
+#[test]
+fn iter_yields_elements_in_order() {
+    let rt = Runtime::new().unwrap();
+    let arr = rt.eval("[10, 20, 30]").unwrap().into_array().unwrap();
+
+    let mut seen = Vec::new();
+    for v in arr.iter() {
+        seen.push(v.unwrap().as_number().unwrap());
+    }
+    assert_eq!(seen, vec![10.0, 20.0, 30.0]);
+    assert_eq!(arr.to_vec::<f64>().unwrap(), vec![10.0, 20.0, 30.0]);
+}
+
+#[test]
+fn iter_reports_throwing_getter() {
+    let rt = Runtime::new().unwrap();
+    let arr = rt
+        .eval(
+            "var a = [1, 2, 3];
+             Object.defineProperty(a, 1, { get() { throw new Error('boom'); } });
+             a",
+        )
+        .unwrap()
+        .into_array()
+        .unwrap();
+
+    let items: Vec<_> = arr.iter().collect();
+    assert_eq!(items.len(), 3);
+    assert_eq!(items[0].as_ref().unwrap().as_number(), Some(1.0));
+    assert_eq!(
+        items[1].as_ref().unwrap_err().to_string(),
+        "JavaScript exception: Error: boom"
+    );
+    assert_eq!(items[2].as_ref().unwrap().as_number(), Some(3.0));
+
+    assert!(arr.to_vec::<f64>().is_err());
+}
+
+#[test]
+fn index_of_and_includes_follow_js_semantics() {
+    let rt = Runtime::new().unwrap();
+    let arr = rt.eval("[1, NaN, 'a']").unwrap().into_array().unwrap();
+
+    let a = rt.eval("'a'").unwrap();
+    assert_eq!(arr.index_of(&a).unwrap(), Some(2));
+    assert!(arr.includes(&a).unwrap());
+
+    let nan = Value::from_number(f64::NAN);
+    assert_eq!(arr.index_of(&nan).unwrap(), None);
+    assert!(arr.includes(&nan).unwrap());
+
+    let missing = Value::from_number(7.0);
+    assert_eq!(arr.index_of(&missing).unwrap(), None);
+    assert!(!arr.includes(&missing).unwrap());
+}
+
+#[test]
+fn push_and_pop() {
+    let rt = Runtime::new().unwrap();
+    let arr = Array::new(&rt, 0).unwrap();
+
+    for n in [1.0, 2.0, 3.0] {
+        arr.push(Value::from_number(n)).unwrap();
+    }
+    assert_eq!(arr.len(), 3);
+
+    assert_eq!(arr.pop().unwrap().as_number(), Some(3.0));
+    assert_eq!(arr.pop().unwrap().as_number(), Some(2.0));
+    assert_eq!(arr.pop().unwrap().as_number(), Some(1.0));
+    assert!(arr.pop().unwrap().is_undefined());
+    assert!(arr.is_empty());
+}
+
+#[test]
+fn reverse_and_slice() {
+    let rt = Runtime::new().unwrap();
+    let arr = rt.eval("[1, 2, 3, 4]").unwrap().into_array().unwrap();
+    arr.reverse().unwrap();
+    assert_eq!(arr.to_vec::<f64>().unwrap(), vec![4.0, 3.0, 2.0, 1.0]);
+
+    assert_eq!(
+        arr.slice(1, 3).unwrap().to_vec::<f64>().unwrap(),
+        vec![3.0, 2.0]
+    );
+    assert_eq!(
+        arr.slice(2, 10).unwrap().to_vec::<f64>().unwrap(),
+        vec![2.0, 1.0]
+    );
+    assert!(arr.slice(3, 1).unwrap().is_empty());
+    assert_eq!(arr.len(), 4);
+}
+
+#[test]
+fn concat_leaves_both_arrays() {
+    let rt = Runtime::new().unwrap();
+    let a = rt.eval("[1, 2]").unwrap().into_array().unwrap();
+    let b = rt.eval("[3, 4]").unwrap().into_array().unwrap();
+    let joined = a.concat(&b).unwrap();
+    assert_eq!(joined.to_vec::<f64>().unwrap(), [1.0, 2.0, 3.0, 4.0]);
+    assert_eq!(a.len(), 2);
+    assert_eq!(b.len(), 2);
+
+    let five = rt.eval("[1, 2, 3, 4, 5]").unwrap().into_array().unwrap();
+    assert_eq!(
+        five.slice(1, 3).unwrap().to_vec::<f64>().unwrap(),
+        [2.0, 3.0]
+    );
+}
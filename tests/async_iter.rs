@@ -0,0 +1,96 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use rusty_hermes::{FromJs, Runtime};
+
+#[test]
+fn for_await_consumes_a_rust_iterator() {
+    let rt = Runtime::new().unwrap();
+    let pulled = Rc::new(Cell::new(0));
+    let counter = pulled.clone();
+    // Each item is produced only when JS asks for it.
+    let items = (0..100).map(move |i| {
+        counter.set(counter.get() + 1);
+        i * 2
+    });
+    let iterable = rt.create_async_iterable(items).unwrap();
+    rt.global().set("items", iterable.into()).unwrap();
+    assert_eq!(pulled.get(), 0);
+
+    let promise = rt
+        .eval(
+            "(async function () {
+                let sum = 0, count = 0;
+                for await (const item of items) {
+                    // Let other microtasks run between items.
+                    await null;
+                    sum += item;
+                    count++;
+                }
+                return [sum, count];
+            })()",
+        )
+        .unwrap()
+        .into_promise()
+        .unwrap();
+    let result = rt.await_promise(&promise).unwrap();
+    assert_eq!(Vec::<f64>::from_js(&result).unwrap(), vec![9900.0, 100.0]);
+    assert_eq!(pulled.get(), 100);
+}
+
+#[test]
+fn items_arrive_in_order_across_host_resolved_delays() {
+    let rt = Runtime::new().unwrap();
+    let pulled = Rc::new(Cell::new(0));
+    let counter = pulled.clone();
+    let items = (0..5).map(move |i| {
+        counter.set(counter.get() + 1);
+        i * 2
+    });
+    let iterable = rt.create_async_iterable(items).unwrap();
+    rt.global().set("items", iterable.into()).unwrap();
+
+    // Each `next()` suspends on a promise that only Rust resolves, standing
+    // in for a timer or IO completion between yields.
+    let promise = rt
+        .eval(
+            "var resolvers = [];
+            var delayed = {
+                next: async function () {
+                    await new Promise(function (resolve) { resolvers.push(resolve); });
+                    return items.next();
+                },
+            };
+            delayed[Symbol.asyncIterator] = function () { return this; };
+            (async function () {
+                const seen = [];
+                for await (const item of delayed) seen.push(item);
+                return seen;
+            })()",
+        )
+        .unwrap()
+        .into_promise()
+        .unwrap();
+
+    let mut delays = 0;
+    loop {
+        rt.drain_microtasks().unwrap();
+        let waiting: f64 = rt.eval_as("resolvers.length").unwrap();
+        if waiting == 0.0 {
+            break;
+        }
+        assert_eq!(waiting, 1.0);
+        // Nothing is pulled while the iterator is suspended.
+        assert_eq!(pulled.get(), delays);
+        rt.eval("resolvers.shift()()").unwrap();
+        delays += 1;
+    }
+    // One delay per item, plus one before the final `done` result.
+    assert_eq!(delays, 6);
+
+    let result = rt.await_promise(&promise).unwrap();
+    assert_eq!(
+        Vec::<f64>::from_js(&result).unwrap(),
+        vec![0.0, 2.0, 4.0, 6.0, 8.0]
+    );
+}
@@ -0,0 +1,71 @@
+use rusty_hermes::{FromJs, JsMap, JsSet, Object, PropertyDescriptor, Runtime, Value};
+
+#[test]
+fn map_built_in_rust_is_readable_from_js() {
+    let rt = Runtime::new().unwrap();
+    let map = JsMap::new(&rt).unwrap();
+    map.set("k", "v").unwrap();
+    map.set(1.0, true).unwrap();
+    assert_eq!(map.size().unwrap(), 2);
+    assert!(map.has(1.0).unwrap());
+    assert!(!map.has("1").unwrap());
+
+    rt.global().set("map", map.clone().into()).unwrap();
+    let got: String = rt.eval_as("map.get('k')").unwrap();
+    assert_eq!(got, "v");
+
+    rt.eval("map.set('fromJs', 3)").unwrap();
+    assert_eq!(map.get("fromJs").unwrap().as_number(), Some(3.0));
+    assert!(map.delete("k").unwrap());
+    assert!(!map.delete("k").unwrap());
+    assert!(map.get("k").unwrap().is_undefined());
+}
+
+#[test]
+fn set_add_has_delete() {
+    let rt = Runtime::new().unwrap();
+    let set = rt.eval("new Set(['a'])").unwrap().into_set().unwrap();
+    set.add("b").unwrap();
+    set.add("b").unwrap();
+    assert_eq!(set.size().unwrap(), 2);
+    assert!(set.has("a").unwrap());
+    assert!(set.delete("a").unwrap());
+    assert!(!set.has("a").unwrap());
+
+    let set = JsSet::new(&rt).unwrap();
+    assert_eq!(set.size().unwrap(), 0);
+}
+
+#[test]
+fn into_map_checks_instances() {
+    let rt = Runtime::new().unwrap();
+    assert!(rt.eval("new Map()").unwrap().into_map().is_ok());
+
+    let err = rt.eval("({ k: 1 })").unwrap().into_map().unwrap_err();
+    assert_eq!(err.message(), "expected Map, got object");
+    assert!(rt.eval("new Map()").unwrap().into_set().is_err());
+    assert!(JsMap::from_js(&Value::Null).is_err());
+}
+
+#[test]
+fn size_ignores_reassigned_globals_and_own_properties() {
+    let rt = Runtime::new().unwrap();
+    let map = JsMap::new(&rt).unwrap();
+    map.set("a", 1.0).unwrap();
+    let set = JsSet::new(&rt).unwrap();
+    set.add("x").unwrap();
+    set.add("y").unwrap();
+
+    rt.eval("Map = function () {}; Set = null;").unwrap();
+    Object::from(map.clone())
+        .define_property(
+            "size",
+            PropertyDescriptor {
+                value: Some(Value::Number(99.0)),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    assert_eq!(map.size().unwrap(), 1);
+    assert_eq!(set.size().unwrap(), 2);
+}
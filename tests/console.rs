@@ -0,0 +1,73 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use rusty_hermes::{ConsoleLevel, Runtime};
+
+#[test]
+fn console_formats_like_a_browser() {
+    let rt = Runtime::new().unwrap();
+    let lines = Rc::new(RefCell::new(Vec::new()));
+    let sink = lines.clone();
+    rt.install_console_with(move |level, line| sink.borrow_mut().push((level, line)))
+        .unwrap();
+
+    rt.eval("console.log('x', 1, true)").unwrap();
+    rt.eval("console.warn({ a: [1, null] }, undefined, 1.5)")
+        .unwrap();
+    rt.eval("console.error(new TypeError('bad'))").unwrap();
+    rt.eval("console.info()").unwrap();
+
+    assert_eq!(
+        *lines.borrow(),
+        [
+            (ConsoleLevel::Log, "x 1 true".to_string()),
+            (
+                ConsoleLevel::Warn,
+                r#"{"a":[1,null]} undefined 1.5"#.to_string()
+            ),
+            (ConsoleLevel::Error, "TypeError: bad".to_string()),
+            (ConsoleLevel::Info, String::new()),
+        ]
+    );
+}
+
+#[test]
+fn cyclic_objects_fall_back_to_string() {
+    let rt = Runtime::new().unwrap();
+    let lines = Rc::new(RefCell::new(Vec::new()));
+    let sink = lines.clone();
+    rt.install_console_with(move |_, line| sink.borrow_mut().push(line))
+        .unwrap();
+
+    rt.eval("var o = {}; o.self = o; console.log('cycle', o)")
+        .unwrap();
+    assert_eq!(*lines.borrow(), ["cycle [object Object]"]);
+}
+
+#[test]
+fn install_console_defines_every_method() {
+    let rt = Runtime::new().unwrap();
+    rt.install_console().unwrap();
+    let kinds: String = rt
+        .eval_as("['log', 'info', 'warn', 'error'].map(m => typeof console[m]).join()")
+        .unwrap();
+    assert_eq!(kinds, "function,function,function,function");
+}
+
+#[test]
+fn console_sink_is_dropped_with_the_runtime() {
+    let lines = Rc::new(RefCell::new(Vec::new()));
+    {
+        let rt = Runtime::new().unwrap();
+        let sink = lines.clone();
+        let mut count = 0;
+        rt.install_console_with(move |_, line| {
+            count += 1;
+            sink.borrow_mut().push(format!("{}: {}", count, line));
+        })
+        .unwrap();
+        rt.eval("console.log('a'); console.error('b')").unwrap();
+    }
+    assert_eq!(*lines.borrow(), ["1: a", "2: b"]);
+    assert_eq!(Rc::strong_count(&lines), 1);
+}
@@ -0,0 +1,241 @@
+use std::borrow::Cow;
+use std::num::{NonZeroI32, NonZeroI64, NonZeroU32, NonZeroU64};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use rusty_hermes::{Error, FromJs, IntoJs, Runtime, Value};
+
+#[test]
+fn char_round_trips() {
+    let rt = Runtime::new().unwrap();
+    let value = 'λ'.into_js(&rt).unwrap();
+    assert_eq!(String::from_js(&value).unwrap(), "λ");
+    assert_eq!(char::from_js(&value).unwrap(), 'λ');
+    assert_eq!(char::from_js(&rt.eval("'😀'").unwrap()).unwrap(), '😀');
+}
+
+#[test]
+fn char_rejects_other_lengths_and_lone_surrogates() {
+    let rt = Runtime::new().unwrap();
+    assert!(matches!(
+        char::from_js(&rt.eval("'ab'").unwrap()),
+        Err(Error::TypeError(_))
+    ));
+    assert!(matches!(
+        char::from_js(&rt.eval("''").unwrap()),
+        Err(Error::TypeError(_))
+    ));
+    assert!(matches!(
+        char::from_js(&rt.eval("'\\uD800'").unwrap()),
+        Err(Error::RuntimeError(_))
+    ));
+    assert_eq!(
+        char::from_js(&rt.eval("'\\uFFFD'").unwrap()).unwrap(),
+        '\u{FFFD}'
+    );
+}
+
+#[test]
+fn duration_is_milliseconds() {
+    let rt = Runtime::new().unwrap();
+    let value = Duration::from_secs(5).into_js(&rt).unwrap();
+    assert_eq!(value.as_number(), Some(5000.0));
+    assert_eq!(Duration::from_js(&value).unwrap(), Duration::from_secs(5));
+    assert!(Duration::from_js(&rt.eval("-1").unwrap()).is_err());
+}
+
+#[test]
+fn system_time_is_a_date() {
+    let rt = Runtime::new().unwrap();
+    let time = UNIX_EPOCH + Duration::from_millis(1500);
+    rt.global().set("t", time.into_js(&rt).unwrap()).unwrap();
+    let iso = String::from_js(&rt.eval("t.toISOString()").unwrap()).unwrap();
+    assert_eq!(iso, "1970-01-01T00:00:01.500Z");
+    assert_eq!(SystemTime::from_js(&rt.eval("t").unwrap()).unwrap(), time);
+
+    let before = SystemTime::from_js(&rt.eval("-2000").unwrap()).unwrap();
+    assert_eq!(before, UNIX_EPOCH - Duration::from_secs(2));
+    assert!(SystemTime::from_js(&rt.eval("new Date(NaN)").unwrap()).is_err());
+    for ms in [1e300, -1e300] {
+        let err = SystemTime::from_js(&Value::Number(ms)).unwrap_err();
+        assert!(matches!(err, Error::TypeError(_)), "{:?}", err);
+    }
+}
+
+#[test]
+fn i64_and_u64_are_exact() {
+    let rt = Runtime::new().unwrap();
+    let big = rt.eval("9007199254740993n").unwrap();
+    let id = i64::from_js(&big).unwrap();
+    assert_eq!(id, 9007199254740993);
+    rt.global().set("id", id.into_js(&rt).unwrap()).unwrap();
+    assert_eq!(
+        rt.eval("id === 9007199254740993n").unwrap().as_bool(),
+        Some(true)
+    );
+
+    let small = 42u64.into_js(&rt).unwrap();
+    assert_eq!(small.as_number(), Some(42.0));
+    assert_eq!(u64::from_js(&small).unwrap(), 42);
+    assert_eq!(
+        u64::from_js(&rt.eval("2n ** 64n - 1n").unwrap()).unwrap(),
+        u64::MAX
+    );
+
+    assert!(i64::from_js(&rt.eval("1.5").unwrap()).is_err());
+    assert!(i64::from_js(&rt.eval("2 ** 60").unwrap()).is_err());
+    assert!(u64::from_js(&rt.eval("-1").unwrap()).is_err());
+    assert!(u64::from_js(&rt.eval("-1n").unwrap()).is_err());
+    assert_eq!(i64::from_js(&rt.eval("-5").unwrap()).unwrap(), -5);
+}
+
+#[test]
+fn non_zero_integers_reject_zero() {
+    let rt = Runtime::new().unwrap();
+    let five = NonZeroU32::new(5).unwrap();
+    let value = five.into_js(&rt).unwrap();
+    assert_eq!(value.as_number(), Some(5.0));
+    assert_eq!(NonZeroU32::from_js(&value).unwrap(), five);
+
+    let err = NonZeroU32::from_js(&Value::Number(0.0)).unwrap_err();
+    assert_eq!(err, Error::RuntimeError("value must be non-zero".into()));
+    assert!(matches!(
+        NonZeroI32::from_js(&Value::Number(1.5)),
+        Err(Error::TypeError(_))
+    ));
+    assert!(NonZeroU32::from_js(&Value::Number(-1.0)).is_err());
+    assert_eq!(NonZeroI32::from_js(&Value::Number(-7.0)).unwrap().get(), -7);
+
+    let big = NonZeroU64::new(u64::MAX).unwrap();
+    assert_eq!(
+        NonZeroU64::from_js(&big.into_js(&rt).unwrap()).unwrap(),
+        big
+    );
+    let min = NonZeroI64::new(i64::MIN).unwrap();
+    assert_eq!(
+        NonZeroI64::from_js(&min.into_js(&rt).unwrap()).unwrap(),
+        min
+    );
+    assert!(NonZeroI64::from_js(&rt.eval("0n").unwrap()).is_err());
+}
+
+#[test]
+fn ranges_are_start_end_objects() {
+    let rt = Runtime::new().unwrap();
+    rt.global()
+        .set("r", (2u32..5).into_js(&rt).unwrap())
+        .unwrap();
+    assert_eq!(
+        String::from_js(&rt.eval("JSON.stringify(r)").unwrap()).unwrap(),
+        r#"{"start":2,"end":5}"#
+    );
+    assert_eq!(
+        std::ops::Range::<u32>::from_js(&rt.eval("r").unwrap()).unwrap(),
+        2..5
+    );
+
+    let inclusive = (1.5f64..=2.5).into_js(&rt).unwrap();
+    rt.global().set("ri", inclusive).unwrap();
+    assert_eq!(
+        String::from_js(&rt.eval("JSON.stringify(ri)").unwrap()).unwrap(),
+        r#"{"start":1.5,"end":2.5,"inclusive":true}"#
+    );
+    assert_eq!(
+        std::ops::RangeInclusive::<f64>::from_js(&rt.eval("ri").unwrap()).unwrap(),
+        1.5..=2.5
+    );
+    assert!(std::ops::Range::<f64>::from_js(&rt.eval("ri").unwrap()).is_err());
+
+    let err = std::ops::Range::<u32>::from_js(&rt.eval("({ start: 1 })").unwrap()).unwrap_err();
+    assert_eq!(err.message(), "at .end: expected number, got undefined");
+}
+
+#[test]
+fn tuples_convert_as_arrays() {
+    let rt = Runtime::new().unwrap();
+    let t: (i32, String, bool) = rt.eval_as("[1, 'a', true]").unwrap();
+    assert_eq!(t, (1, "a".to_string(), true));
+
+    let one: (f64,) = rt.eval_as("[1.5]").unwrap();
+    assert_eq!(one, (1.5,));
+    let two: (String, f64) = rt.eval_as("['x', 2]").unwrap();
+    assert_eq!(two, ("x".to_string(), 2.0));
+    let four: (i32, i32, i32, i32) = rt.eval_as("[1, 2, 3, 4]").unwrap();
+    assert_eq!(four, (1, 2, 3, 4));
+    let five: (i32, i32, i32, i32, bool) = rt.eval_as("[1, 2, 3, 4, false]").unwrap();
+    assert_eq!(five, (1, 2, 3, 4, false));
+    let six: (i32, i32, i32, i32, i32, Option<i32>) = rt.eval_as("[1, 2, 3, 4, 5, null]").unwrap();
+    assert_eq!(six, (1, 2, 3, 4, 5, None));
+    let seven: (i32, i32, i32, i32, i32, i32, String) =
+        rt.eval_as("[1, 2, 3, 4, 5, 6, '7']").unwrap();
+    assert_eq!(seven, (1, 2, 3, 4, 5, 6, "7".to_string()));
+
+    let eight = (1, 2, 3, 4, 5, 6, 7, "eight".to_string());
+    rt.global()
+        .set("eight", eight.clone().into_js(&rt).unwrap())
+        .unwrap();
+    let joined: String = rt.eval_as("eight.join()").unwrap();
+    assert_eq!(joined, "1,2,3,4,5,6,7,eight");
+    let back: (i32, i32, i32, i32, i32, i32, i32, String) = rt.eval_as("eight").unwrap();
+    assert_eq!(back, eight);
+}
+
+#[test]
+fn tuples_check_length_and_elements() {
+    let rt = Runtime::new().unwrap();
+    let err = rt.eval_as::<(i32, i32)>("[1, 2, 3]").unwrap_err();
+    assert_eq!(err.message(), "expected an array of length 2, got length 3");
+
+    let err = rt.eval_as::<(i32, bool)>("[1, 'no']").unwrap_err();
+    assert_eq!(err.message(), "at [1]: expected boolean, got string");
+}
+
+#[test]
+fn slices_and_boxed_slices() {
+    let rt = Runtime::new().unwrap();
+    let v: &[i32] = &[1, 2, 3];
+    rt.global().set("v", v.into_js(&rt).unwrap()).unwrap();
+    let joined: String = rt.eval_as("Array.isArray(v) && v.join('-')").unwrap();
+    assert_eq!(joined, "1-2-3");
+
+    let boxed: Box<[f64]> = rt.eval_as("[0.5, 1.5]").unwrap();
+    assert_eq!(&*boxed, &[0.5, 1.5]);
+    let empty: Box<[f64]> = rt.eval_as("[]").unwrap();
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn cow_str_roundtrips() {
+    let rt = Runtime::new().unwrap();
+    let borrowed: Cow<str> = Cow::Borrowed("borrowed");
+    let owned: Cow<str> = Cow::Owned(String::from("owned"));
+    for cow in [borrowed, owned] {
+        let expected = cow.to_string();
+        let value = cow.into_js(&rt).unwrap();
+        let back = Cow::<'static, str>::from_js(&value).unwrap();
+        assert!(matches!(back, Cow::Owned(_)));
+        assert_eq!(back, expected);
+    }
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn serde_json_values_round_trip() {
+    let rt = Runtime::new().unwrap();
+    let json = serde_json::json!({"a": [1, 2], "b": null, "c": "s"});
+    rt.global()
+        .set("doc", json.clone().into_js(&rt).unwrap())
+        .unwrap();
+    assert_eq!(rt.eval_as::<f64>("doc.a[1]").unwrap(), 2.0);
+    assert_eq!(rt.eval_as::<String>("doc.c").unwrap(), "s");
+    assert!(rt.eval("doc.b").unwrap().is_null());
+
+    let back: serde_json::Value = rt.eval_as("doc").unwrap();
+    assert_eq!(back, json);
+
+    let read: serde_json::Value = rt.eval_as("({ x: 1.5, y: undefined, z: [NaN] })").unwrap();
+    assert_eq!(read, serde_json::json!({"x": 1.5, "z": [null]}));
+    assert_eq!(
+        serde_json::Value::from_js(&rt.eval("undefined").unwrap()).unwrap(),
+        serde_json::Value::Null
+    );
+}
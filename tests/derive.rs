@@ -0,0 +1,275 @@
+use rusty_hermes::{FromJs, IntoJs, Runtime, Value};
+
+#[derive(Debug, Clone, PartialEq, IntoJs, FromJs)]
+#[hermes(tag = "kind", rename_all = "lowercase")]
+enum Shape {
+    Circle {
+        radius: f64,
+    },
+    #[hermes(rename = "rect")]
+    Rectangle {
+        #[hermes(rename = "w")]
+        width: f64,
+        height: f64,
+    },
+    Empty,
+}
+
+#[derive(Debug, PartialEq, IntoJs, FromJs)]
+#[hermes(tag = "kind")]
+enum Geometry {
+    Circle { radius: f64 },
+    Origin,
+}
+
+#[derive(Debug, PartialEq, IntoJs, FromJs)]
+enum Message {
+    Quit,
+    Move(i32, i32),
+    Write(String),
+    #[hermes(rename_all = "camelCase")]
+    Resize {
+        new_width: u32,
+    },
+}
+
+#[derive(Debug, PartialEq, FromJs)]
+struct Point {
+    x: f64,
+    y: f64,
+}
+
+#[derive(Debug, PartialEq, FromJs)]
+struct Segment {
+    start: Point,
+    end: Point,
+}
+
+#[derive(Debug, PartialEq, IntoJs, FromJs)]
+#[hermes(rename_all = "camelCase")]
+struct Req {
+    user_id: i32,
+    #[hermes(rename = "ttl")]
+    time_to_live: u32,
+}
+
+fn default_limit() -> u32 {
+    10
+}
+
+#[derive(Debug, PartialEq, FromJs)]
+struct Query {
+    name: String,
+    #[hermes(default)]
+    count: i32,
+    #[hermes(default = "default_limit")]
+    limit: u32,
+}
+
+fn to_json(rt: &Runtime, value: Value<'_>) -> String {
+    rt.global().set("__v", value).unwrap();
+    String::from_js(&rt.eval("JSON.stringify(__v)").unwrap()).unwrap()
+}
+
+#[test]
+fn internally_tagged_enum_to_js() {
+    let rt = Runtime::new().unwrap();
+    let circle = Shape::Circle { radius: 5.0 }.into_js(&rt).unwrap();
+    assert_eq!(to_json(&rt, circle), r#"{"kind":"circle","radius":5}"#);
+    let rect = Shape::Rectangle {
+        width: 2.0,
+        height: 3.0,
+    }
+    .into_js(&rt)
+    .unwrap();
+    assert_eq!(to_json(&rt, rect), r#"{"kind":"rect","w":2,"height":3}"#);
+    let empty = Shape::Empty.into_js(&rt).unwrap();
+    assert_eq!(to_json(&rt, empty), r#"{"kind":"empty"}"#);
+}
+
+#[test]
+fn internally_tagged_enum_round_trip() {
+    let rt = Runtime::new().unwrap();
+    for shape in [
+        Shape::Circle { radius: 1.5 },
+        Shape::Rectangle {
+            width: 4.0,
+            height: 2.0,
+        },
+        Shape::Empty,
+    ] {
+        let value = shape.clone().into_js(&rt).unwrap();
+        assert_eq!(Shape::from_js(&value).unwrap(), shape);
+    }
+
+    let parsed = Shape::from_js(&rt.eval("({ kind: 'rect', w: 1, height: 2 })").unwrap());
+    assert_eq!(
+        parsed.unwrap(),
+        Shape::Rectangle {
+            width: 1.0,
+            height: 2.0
+        }
+    );
+}
+
+#[test]
+fn internally_tagged_enum_errors() {
+    let rt = Runtime::new().unwrap();
+    let err = Shape::from_js(&rt.eval("({ radius: 1 })").unwrap()).unwrap_err();
+    assert_eq!(err.message(), "missing tag field `kind` of enum `Shape`");
+    let err = Shape::from_js(&rt.eval("({ kind: 'Circle' })").unwrap()).unwrap_err();
+    assert_eq!(err.message(), "unknown variant `Circle` of enum `Shape`");
+}
+
+#[test]
+fn internally_tagged_enum_keeps_variant_names() {
+    let rt = Runtime::new().unwrap();
+    let cases = [
+        (
+            Geometry::Circle { radius: 5.0 },
+            r#"{"kind":"Circle","radius":5}"#,
+        ),
+        (Geometry::Origin, r#"{"kind":"Origin"}"#),
+    ];
+    for (geometry, json) in cases {
+        let source = format!("({})", json);
+        assert_eq!(
+            Geometry::from_js(&rt.eval(&source).unwrap()).unwrap(),
+            geometry
+        );
+        assert_eq!(to_json(&rt, geometry.into_js(&rt).unwrap()), json);
+    }
+    let err = Geometry::from_js(&rt.eval("({ type: 'Circle', radius: 5 })").unwrap()).unwrap_err();
+    assert_eq!(err.message(), "missing tag field `kind` of enum `Geometry`");
+}
+
+#[test]
+fn externally_tagged_enum_round_trip() {
+    let rt = Runtime::new().unwrap();
+    let cases = [
+        (Message::Quit, r#""Quit""#),
+        (Message::Move(1, -2), r#"{"Move":[1,-2]}"#),
+        (Message::Write("hi".into()), r#"{"Write":"hi"}"#),
+        (
+            Message::Resize { new_width: 640 },
+            r#"{"Resize":{"newWidth":640}}"#,
+        ),
+    ];
+    for (message, json) in cases {
+        let source = format!("({})", json);
+        assert_eq!(
+            Message::from_js(&rt.eval(&source).unwrap()).unwrap(),
+            message
+        );
+        assert_eq!(to_json(&rt, message.into_js(&rt).unwrap()), json);
+    }
+}
+
+#[test]
+fn errors_carry_the_path() {
+    let rt = Runtime::new().unwrap();
+    let err = Segment::from_js(
+        &rt.eval("({ start: { x: '1', y: 2 }, end: { x: 3, y: 4 } })")
+            .unwrap(),
+    )
+    .unwrap_err();
+    assert_eq!(err.message(), "at .start.x: expected number, got string");
+
+    let err = Vec::<Segment>::from_js(
+        &rt.eval("[{ start: { x: 1, y: 2 }, end: { x: 3, y: 4 } }, { start: { x: 1, y: 2 }, end: null }]")
+            .unwrap(),
+    )
+    .unwrap_err();
+    assert_eq!(err.message(), "at [1].end: expected object, got null");
+
+    let err = Message::from_js(&rt.eval("({ Move: [1, 'up'] })").unwrap()).unwrap_err();
+    assert_eq!(err.message(), "at .Move[1]: expected number, got string");
+}
+
+#[test]
+fn struct_fields_are_renamed_both_ways() {
+    let rt = Runtime::new().unwrap();
+    let req = Req {
+        user_id: 7,
+        time_to_live: 30,
+    };
+    let value = Req {
+        user_id: 7,
+        time_to_live: 30,
+    }
+    .into_js(&rt)
+    .unwrap();
+    assert_eq!(to_json(&rt, value), r#"{"userId":7,"ttl":30}"#);
+    assert_eq!(
+        Req::from_js(&rt.eval("({ userId: 7, ttl: 30 })").unwrap()).unwrap(),
+        req
+    );
+    assert!(Req::from_js(&rt.eval("({ user_id: 7, ttl: 30 })").unwrap()).is_err());
+}
+
+#[test]
+fn missing_fields_use_defaults() {
+    let rt = Runtime::new().unwrap();
+    let query = Query::from_js(&rt.eval("({ name: 'q' })").unwrap()).unwrap();
+    assert_eq!(
+        query,
+        Query {
+            name: "q".into(),
+            count: 0,
+            limit: 10,
+        }
+    );
+    let query = Query::from_js(
+        &rt.eval("({ name: 'q', count: 3, limit: undefined })")
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!((query.count, query.limit), (3, 10));
+
+    let err = Query::from_js(&rt.eval("({ count: 3 })").unwrap()).unwrap_err();
+    assert_eq!(err.message(), "at .name: expected string, got undefined");
+    assert!(Query::from_js(&rt.eval("({ name: 'q', count: null })").unwrap()).is_err());
+}
+
+/// Derives used the way a downstream crate would: through `rusty_hermes`
+/// paths only, next to local items that shadow names the generated code
+/// might otherwise pick up.
+mod downstream {
+    #[allow(dead_code)]
+    struct Value;
+    #[allow(dead_code)]
+    type Result<T> = core::result::Result<T, ()>;
+    #[allow(dead_code)]
+    trait FromJs {}
+
+    #[derive(Debug, PartialEq, rusty_hermes::IntoJs, rusty_hermes::FromJs)]
+    pub struct Config {
+        pub name: String,
+        #[hermes(default)]
+        pub retries: u32,
+    }
+
+    #[derive(Debug, PartialEq, rusty_hermes::IntoJs, rusty_hermes::FromJs)]
+    pub enum Mode {
+        Fast,
+        Limit(u32),
+    }
+}
+
+#[test]
+fn derives_expand_with_crate_paths_only() {
+    use downstream::{Config, Mode};
+
+    let rt = Runtime::new().unwrap();
+    let config: Config = rt.eval_as("({ name: 'svc' })").unwrap();
+    assert_eq!(
+        config,
+        Config {
+            name: "svc".into(),
+            retries: 0
+        }
+    );
+
+    let mode = Mode::Limit(3).into_js(&rt).unwrap();
+    assert_eq!(Mode::from_js(&mode).unwrap(), Mode::Limit(3));
+}
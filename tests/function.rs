@@ -0,0 +1,186 @@
+use rusty_hermes::{Error, Function, JsErrorKind, Runtime, Value};
+
+fn parse(s: String) -> rusty_hermes::Result<f64> {
+    s.parse::<f64>()
+        .map_err(|_| Error::RuntimeError(format!("cannot parse {:?}", s)))
+}
+
+#[test]
+fn set_func_calls_closure() {
+    let rt = Runtime::new().unwrap();
+    rt.set_func("add", |a: f64, b: f64| a + b).unwrap();
+
+    let v = rt.eval("add(1, 2)").unwrap();
+    assert_eq!(v.as_number(), Some(3.0));
+}
+
+#[test]
+fn create_func_can_be_installed_anywhere() {
+    let rt = Runtime::new().unwrap();
+    let add = rt.create_func("add", |a: f64, b: f64| a + b).unwrap();
+    assert!(rt.global().get("add").unwrap().is_undefined());
+
+    let my_obj = rt
+        .eval("globalThis.myObj = {}")
+        .unwrap()
+        .into_object()
+        .unwrap();
+    my_obj.set("add", add.clone().into()).unwrap();
+    assert_eq!(rt.eval("myObj.add(1, 2)").unwrap().as_number(), Some(3.0));
+    assert_eq!(
+        add.call(&[Value::Number(2.0), Value::Number(5.0)])
+            .unwrap()
+            .as_number(),
+        Some(7.0)
+    );
+}
+
+#[test]
+fn set_func_throwing_uses_error_kind() {
+    let rt = Runtime::new().unwrap();
+    rt.set_func_throwing("parse", parse, JsErrorKind::RangeError)
+        .unwrap();
+
+    let ok = rt.eval("parse('1.5')").unwrap();
+    assert_eq!(ok.as_number(), Some(1.5));
+
+    let caught = rt
+        .eval("try { parse('x'); 'no error' } catch (e) { e.name + ': ' + e.message }")
+        .unwrap();
+    assert_eq!(
+        caught.into_string().unwrap().to_rust_string().unwrap(),
+        "RangeError: cannot parse \"x\""
+    );
+}
+
+#[test]
+fn set_func_error_defaults_to_plain_error() {
+    let rt = Runtime::new().unwrap();
+    rt.set_func("parse", parse).unwrap();
+
+    let err = rt.eval("parse('x')").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "JavaScript exception: Error: cannot parse \"x\""
+    );
+}
+
+#[test]
+fn invoke_converts_this_and_args() {
+    let rt = Runtime::new().unwrap();
+    let add = rt.eval("(function (a, b) { return a + b })").unwrap();
+    let add = add.into_function().unwrap();
+    let joined = add.invoke().arg(1.0).arg("hi").call().unwrap();
+    assert_eq!(
+        joined.into_string().unwrap().to_rust_string().unwrap(),
+        "1hi"
+    );
+
+    let obj = rt.eval("({ base: 10 })").unwrap().into_object().unwrap();
+    let method = rt.eval("(function (n) { return this.base + n })").unwrap();
+    let method = method.into_function().unwrap();
+    let sum = method.invoke().this(&obj).arg(5).call().unwrap();
+    assert_eq!(sum.as_number(), Some(15.0));
+}
+
+#[test]
+fn panics_are_thrown_with_their_message() {
+    let rt = Runtime::new().unwrap();
+    rt.set_func("explode", |n: f64| -> f64 { panic!("bad input {}", n) })
+        .unwrap();
+
+    let err = rt.eval("explode(3)").unwrap_err();
+    assert!(
+        err.message()
+            .starts_with("host function panicked: bad input 3"),
+        "{}",
+        err
+    );
+    // The runtime is still usable.
+    assert_eq!(rt.eval("1 + 1").unwrap().as_number(), Some(2.0));
+}
+
+#[test]
+fn weak_function_calls_back_while_alive() {
+    let rt = Runtime::new().unwrap();
+    let handler = rt
+        .eval("globalThis.seen = []; globalThis.handler = function (x) { seen.push(x); }")
+        .unwrap()
+        .into_function()
+        .unwrap();
+    let weak = handler.downgrade().unwrap();
+    drop(handler);
+
+    let emit = Function::from_host_fn(&rt, "emit", 1, move |rt, _this, args| {
+        if let Some(handler) = weak.lock(rt)? {
+            handler.call(args)?;
+        }
+        Ok(Value::Undefined)
+    })
+    .unwrap();
+    rt.global().set("emit", emit.into()).unwrap();
+
+    rt.eval("emit(1); emit(2)").unwrap();
+    // Once JS drops the handler, the host function does not keep it alive.
+    rt.eval("delete globalThis.handler").unwrap();
+    rt.collect_garbage().unwrap();
+    rt.eval("emit(3)").unwrap();
+
+    let seen: Vec<f64> = rt.eval_as("seen").unwrap();
+    assert_eq!(seen, [1.0, 2.0]);
+}
+
+#[test]
+fn weak_function_rejects_other_runtimes() {
+    let rt = Runtime::new().unwrap();
+    let other = Runtime::new().unwrap();
+    let func = rt
+        .eval("(function () {})")
+        .unwrap()
+        .into_function()
+        .unwrap();
+    let weak = func.downgrade().unwrap();
+    assert!(weak.lock(&rt).unwrap().is_some());
+    assert!(weak.lock(&other).is_err());
+}
+
+#[test]
+fn bind_fixes_this_and_leading_args() {
+    let rt = Runtime::new().unwrap();
+    let add = rt
+        .eval("(function (a, b) { return this.k + a + b })")
+        .unwrap()
+        .into_function()
+        .unwrap();
+    let this = rt.eval("({ k: 10 })").unwrap();
+    let bound = add.bind(&this, &[Value::Number(1.0)]).unwrap();
+    let sum = bound.call(&[Value::Number(2.0)]).unwrap();
+    assert_eq!(sum.as_number(), Some(13.0));
+
+    rt.global().set("bound", bound.into()).unwrap();
+    assert_eq!(rt.eval("bound.length").unwrap().as_number(), Some(1.0));
+}
+
+#[test]
+fn name_and_arity() {
+    let rt = Runtime::new().unwrap();
+    let foo = rt
+        .eval("(function foo(a, b) {})")
+        .unwrap()
+        .into_function()
+        .unwrap();
+    assert_eq!(foo.name().unwrap(), "foo");
+    assert_eq!(foo.arity().unwrap(), 2);
+
+    let host = Function::from_host_fn(&rt, "hostAdd", 3, |_rt, _this, _args| Ok(Value::Undefined))
+        .unwrap();
+    assert_eq!(host.name().unwrap(), "hostAdd");
+    assert_eq!(host.arity().unwrap(), 3);
+
+    let anonymous = rt
+        .eval("[function () {}][0]")
+        .unwrap()
+        .into_function()
+        .unwrap();
+    assert_eq!(anonymous.name().unwrap(), "");
+}
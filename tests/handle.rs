@@ -0,0 +1,58 @@
+use std::sync::Arc;
+use std::thread;
+
+use rusty_hermes::{FromJs, RuntimeHandle};
+
+#[test]
+fn handle_is_send_and_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<RuntimeHandle>();
+}
+
+#[test]
+fn threads_share_one_runtime() {
+    let handle = Arc::new(RuntimeHandle::spawn().unwrap());
+    handle
+        .with(|rt| rt.eval("globalThis.total = 0").map(drop))
+        .unwrap()
+        .unwrap();
+
+    let workers: Vec<_> = (0..2)
+        .map(|_| {
+            let handle = Arc::clone(&handle);
+            thread::spawn(move || {
+                for _ in 0..50 {
+                    handle
+                        .with(|rt| rt.eval("total += 1").map(drop))
+                        .unwrap()
+                        .unwrap();
+                }
+            })
+        })
+        .collect();
+    for worker in workers {
+        worker.join().unwrap();
+    }
+
+    let total = handle
+        .with(|rt| f64::from_js(&rt.eval("total")?))
+        .unwrap()
+        .unwrap();
+    assert_eq!(total, 100.0);
+}
+
+#[test]
+fn panics_propagate_and_runtime_survives() {
+    let handle = RuntimeHandle::spawn().unwrap();
+    let caught = thread::scope(|s| {
+        s.spawn(|| handle.with(|_| panic!("boom")).unwrap())
+            .join()
+            .is_err()
+    });
+    assert!(caught);
+    let sum = handle
+        .with(|rt| rt.eval("1 + 2").map(|v| v.as_number()))
+        .unwrap()
+        .unwrap();
+    assert_eq!(sum, Some(3.0));
+}
@@ -0,0 +1,115 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use rusty_hermes::{Error, HostObject, Object, PropNameId, Runtime, Value};
+
+#[derive(Clone)]
+struct Counted {
+    reads: Rc<Cell<u32>>,
+}
+
+impl<'rt> rusty_hermes::IntoJs<'rt> for Counted {
+    fn into_js(self, _rt: &'rt Runtime) -> rusty_hermes::Result<Value<'rt>> {
+        self.reads.set(self.reads.get() + 1);
+        Ok(Value::Number(self.reads.get() as f64))
+    }
+}
+
+#[derive(HostObject)]
+#[hermes(rename_all = "camelCase")]
+struct Config {
+    max_retries: u32,
+    server_name: String,
+    expensive: Counted,
+}
+
+#[test]
+fn derived_fields_are_converted_on_read() {
+    let rt = Runtime::new().unwrap();
+    let reads = Rc::new(Cell::new(0));
+    let config = Config {
+        max_retries: 3,
+        server_name: "api".into(),
+        expensive: Counted {
+            reads: reads.clone(),
+        },
+    };
+    let obj = Object::from_host_object(&rt, config).unwrap();
+    rt.global().set("config", obj.into()).unwrap();
+
+    let retries = rt.eval("config.maxRetries").unwrap();
+    assert_eq!(retries.as_number(), Some(3.0));
+    assert_eq!(reads.get(), 0);
+
+    let keys = rt.eval("Object.keys(config).join()").unwrap();
+    assert_eq!(
+        keys.into_string().unwrap().to_rust_string().unwrap(),
+        "maxRetries,serverName,expensive"
+    );
+    assert!(rt
+        .eval("config.missing === undefined")
+        .unwrap()
+        .as_bool()
+        .unwrap());
+
+    rt.eval("config.expensive").unwrap();
+    assert_eq!(reads.get(), 1);
+}
+
+struct Store {
+    value: Cell<f64>,
+}
+
+impl HostObject for Store {
+    fn get<'rt>(
+        &self,
+        _rt: &'rt Runtime,
+        name: &PropNameId<'rt>,
+    ) -> rusty_hermes::Result<Value<'rt>> {
+        match name.to_rust_string()?.as_str() {
+            "value" => Ok(Value::Number(self.value.get())),
+            _ => Ok(Value::Undefined),
+        }
+    }
+
+    fn set<'rt>(
+        &self,
+        _rt: &'rt Runtime,
+        name: &PropNameId<'rt>,
+        value: Value<'rt>,
+    ) -> rusty_hermes::Result<()> {
+        match (name.to_rust_string()?.as_str(), value.as_number()) {
+            ("value", Some(n)) => {
+                self.value.set(n);
+                Ok(())
+            }
+            _ => Err(Error::TypeError(
+                "only `value` can be set to a number".into(),
+            )),
+        }
+    }
+}
+
+#[test]
+fn host_object_set_and_errors() {
+    let rt = Runtime::new().unwrap();
+    let store = Object::from_host_object(
+        &rt,
+        Store {
+            value: Cell::new(0.0),
+        },
+    )
+    .unwrap();
+    rt.global().set("store", store.into()).unwrap();
+
+    let value = rt.eval("store.value = 41; store.value + 1").unwrap();
+    assert_eq!(value.as_number(), Some(42.0));
+
+    let caught = rt
+        .eval("try { store.other = 1; 'no error' } catch (e) { e.name }")
+        .unwrap();
+    assert_eq!(
+        caught.into_string().unwrap().to_rust_string().unwrap(),
+        "TypeError"
+    );
+}
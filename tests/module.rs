@@ -0,0 +1,112 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use rusty_hermes::{Error, FromJs, ModuleLoader, ModuleRegistry, Runtime};
+
+struct MemoryLoader {
+    sources: HashMap<&'static str, &'static str>,
+    loads: Rc<RefCell<Vec<String>>>,
+}
+
+impl ModuleLoader for MemoryLoader {
+    fn resolve(&self, specifier: &str, _referrer: Option<&str>) -> rusty_hermes::Result<String> {
+        Ok(format!("mem:{}", specifier.trim_start_matches("./")))
+    }
+
+    fn load(&self, url: &str) -> rusty_hermes::Result<String> {
+        self.loads.borrow_mut().push(url.to_string());
+        self.sources
+            .get(url)
+            .map(|source| source.to_string())
+            .ok_or_else(|| Error::RuntimeError(format!("no module at {}", url)))
+    }
+}
+
+fn registry(rt: &Runtime) -> (ModuleRegistry<'_>, Rc<RefCell<Vec<String>>>) {
+    let loads = Rc::new(RefCell::new(Vec::new()));
+    let loader = MemoryLoader {
+        sources: HashMap::from([
+            ("mem:counter", "var n = 0; exports.next = function () { return ++n; };"),
+            (
+                "mem:main",
+                "var counter = require('./counter'); module.exports = counter.next() + counter.next();",
+            ),
+            ("mem:broken", "throw new Error('broken');"),
+        ]),
+        loads: loads.clone(),
+    };
+    (ModuleRegistry::new(rt, loader).unwrap(), loads)
+}
+
+#[test]
+fn modules_are_cached_by_url() {
+    let rt = Runtime::new().unwrap();
+    let (modules, loads) = registry(&rt);
+
+    assert_eq!(f64::from_js(&modules.import("main").unwrap()).unwrap(), 3.0);
+    let counter = modules.import("./counter").unwrap().into_object().unwrap();
+    let next = counter.call_method("next", &[]).unwrap();
+    assert_eq!(next.as_number(), Some(3.0));
+
+    assert!(modules.is_cached("mem:counter").unwrap());
+    assert_eq!(*loads.borrow(), ["mem:main", "mem:counter"]);
+}
+
+#[test]
+fn failed_modules_are_not_cached() {
+    let rt = Runtime::new().unwrap();
+    let (modules, loads) = registry(&rt);
+
+    let err = modules.import("broken").unwrap_err();
+    assert_eq!(err.to_string(), "JavaScript exception: Error: broken");
+    assert!(!modules.is_cached("mem:broken").unwrap());
+    assert!(modules.import("broken").is_err());
+    assert_eq!(loads.borrow().len(), 2);
+
+    assert!(modules.import("missing").is_err());
+}
+
+#[test]
+fn install_require_defines_a_global() {
+    let rt = Runtime::new().unwrap();
+    let loads = Rc::new(RefCell::new(0));
+    let counted = loads.clone();
+    rt.install_require(move |id| {
+        *counted.borrow_mut() += 1;
+        match id {
+            "math" => Ok("exports.square = function (x) { return x * x; };".into()),
+            "app" => Ok("var math = require('math'); module.exports = math.square(4);".into()),
+            _ => Err(Error::RuntimeError(format!("unknown module {}", id))),
+        }
+    })
+    .unwrap();
+
+    let result = rt
+        .eval("require('app') + require('math').square(2) + require('app')")
+        .unwrap();
+    assert_eq!(result.as_number(), Some(36.0));
+    assert_eq!(*loads.borrow(), 2);
+    assert!(rt.eval("require('nope')").is_err());
+}
+
+#[test]
+fn eval_module_returns_exports() {
+    let rt = Runtime::new().unwrap();
+    let exports = rt
+        .eval_module(
+            "let hidden = 40; exports.default = hidden + 2;",
+            "answer.js",
+        )
+        .unwrap()
+        .into_object()
+        .unwrap();
+    assert_eq!(exports.get("default").unwrap().as_number(), Some(42.0));
+    let hidden: String = rt.eval_as("typeof hidden").unwrap();
+    assert_eq!(hidden, "undefined");
+
+    let replaced = rt
+        .eval_module("module.exports = 'whole';", "whole.js")
+        .unwrap();
+    assert_eq!(String::from_js(&replaced).unwrap(), "whole");
+}
@@ -0,0 +1,288 @@
+use rusty_hermes::{FromJs, Function, IntoJs, Object, PropertyDescriptor, Runtime, Value};
+
+#[test]
+fn keys_and_entries_follow_object_keys() {
+    let rt = Runtime::new().unwrap();
+    let obj = rt
+        .eval("var o = Object.create({ inherited: 0 }); o.x = 1; o.y = 2; o")
+        .unwrap()
+        .into_object()
+        .unwrap();
+    assert_eq!(obj.keys().unwrap(), ["x", "y"]);
+
+    let entries = obj.entries().unwrap();
+    let entries: Vec<(String, Option<f64>)> = entries
+        .into_iter()
+        .map(|(key, value)| (key, value.as_number()))
+        .collect();
+    assert_eq!(
+        entries,
+        [("x".to_string(), Some(1.0)), ("y".to_string(), Some(2.0))]
+    );
+}
+
+#[test]
+fn method_chain_calls_through_results() {
+    let rt = Runtime::new().unwrap();
+    let obj = rt
+        .eval("({ n: 1, inc(by) { return { n: this.n + by, inc: this.inc, done() { return this.n; } }; } })")
+        .unwrap()
+        .into_object()
+        .unwrap();
+    let n = obj
+        .method("inc", &[Value::Number(2.0)])
+        .unwrap()
+        .method("inc", &[Value::Number(3.0)])
+        .unwrap()
+        .method("done", &[])
+        .unwrap()
+        .value();
+    assert_eq!(n.as_number(), Some(6.0));
+
+    let err = obj
+        .method("inc", &[Value::Number(1.0)])
+        .unwrap()
+        .method("done", &[])
+        .unwrap()
+        .method("inc", &[])
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.message(),
+        "`inc().done()` returned number, not an object"
+    );
+
+    let err = obj.method("missing", &[]).err().unwrap();
+    assert_eq!(err.message(), "`missing` is not a function, got undefined");
+}
+
+#[test]
+fn call_method_keeps_the_object() {
+    let rt = Runtime::new().unwrap();
+    let obj = rt
+        .eval("({ x: 7, getX() { return this.x; } })")
+        .unwrap()
+        .into_object()
+        .unwrap();
+    assert_eq!(obj.call_method("getX", &[]).unwrap().as_number(), Some(7.0));
+    obj.set("x", Value::Number(8.0)).unwrap();
+    assert_eq!(obj.call_method("getX", &[]).unwrap().as_number(), Some(8.0));
+
+    let err = obj.call_method("x", &[]).unwrap_err();
+    assert_eq!(err.message(), "`x` is not a function, got number");
+}
+
+#[test]
+fn index_access_on_array_likes() {
+    let rt = Runtime::new().unwrap();
+    let obj = rt
+        .eval("({ 0: 'a', 1: 'b', length: 2 })")
+        .unwrap()
+        .into_object()
+        .unwrap();
+    assert_eq!(String::from_js(&obj.get_index(1).unwrap()).unwrap(), "b");
+    assert!(obj.get_index(2).unwrap().is_undefined());
+
+    obj.set_index(2, Value::Number(3.0)).unwrap();
+    assert_eq!(obj.get("2").unwrap().as_number(), Some(3.0));
+    obj.set_index(u32::MAX, Value::Number(4.0)).unwrap();
+    assert_eq!(obj.get("4294967295").unwrap().as_number(), Some(4.0));
+    obj.set_index(0, Value::Number(0.5)).unwrap();
+    assert_eq!(obj.get_index(0).unwrap().as_number(), Some(0.5));
+
+    let frozen = rt
+        .eval("Object.freeze([1])")
+        .unwrap()
+        .into_object()
+        .unwrap();
+    assert!(frozen.set_index(0, Value::Number(2.0)).is_err());
+}
+
+#[test]
+fn freeze_and_seal() {
+    let rt = Runtime::new().unwrap();
+    let config = rt
+        .eval("({ mode: 'safe' })")
+        .unwrap()
+        .into_object()
+        .unwrap();
+    assert!(!config.is_frozen().unwrap());
+
+    config.freeze().unwrap();
+    assert!(config.is_frozen().unwrap());
+    assert!(config.is_sealed().unwrap());
+    let _ = config.set("mode", "open".into_js(&rt).unwrap());
+    assert_eq!(
+        String::from_js(&config.get("mode").unwrap()).unwrap(),
+        "safe"
+    );
+
+    let sealed = rt.eval("({ n: 1 })").unwrap().into_object().unwrap();
+    sealed.seal().unwrap();
+    assert!(sealed.is_sealed().unwrap());
+    assert!(!sealed.is_frozen().unwrap());
+    sealed.set("n", Value::Number(2.0)).unwrap();
+    let _ = sealed.set("extra", Value::Number(3.0));
+    assert_eq!(sealed.get("n").unwrap().as_number(), Some(2.0));
+    assert!(!sealed.has("extra").unwrap());
+}
+
+#[test]
+fn define_property_with_getter() {
+    let rt = Runtime::new().unwrap();
+    let obj = rt
+        .eval("({ width: 3, height: 4 })")
+        .unwrap()
+        .into_object()
+        .unwrap();
+    let area = Function::from_host_fn(&rt, "area", 0, |_, this, _| {
+        let this = this.clone().into_object()?;
+        let width = f64::from_js(&this.get("width")?)?;
+        let height = f64::from_js(&this.get("height")?)?;
+        Ok(Value::Number(width * height))
+    })
+    .unwrap();
+    obj.define_property(
+        "area",
+        PropertyDescriptor {
+            get: Some(area),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    assert_eq!(obj.get("area").unwrap().as_number(), Some(12.0));
+    obj.set("width", Value::Number(5.0)).unwrap();
+    assert_eq!(obj.get("area").unwrap().as_number(), Some(20.0));
+
+    let names: Vec<String> = obj.property_names().unwrap().to_vec().unwrap();
+    assert_eq!(names, ["width", "height"]);
+
+    let mixed = PropertyDescriptor {
+        value: Some(Value::Number(1.0)),
+        get: Some(
+            rt.eval("(function () {})")
+                .unwrap()
+                .into_function()
+                .unwrap(),
+        ),
+        ..Default::default()
+    };
+    assert!(obj.define_property("bad", mixed).is_err());
+}
+
+#[test]
+fn assign_merges_sources_in_order() {
+    let rt = Runtime::new().unwrap();
+    let target = rt.eval("({ a: 1 })").unwrap().into_object().unwrap();
+    let first = rt.eval("({ a: 2, b: 3 })").unwrap().into_object().unwrap();
+    target.assign(&[&first]).unwrap();
+    rt.global().set("merged", target.clone().into()).unwrap();
+    let json: String = rt.eval_as("JSON.stringify(merged)").unwrap();
+    assert_eq!(json, r#"{"a":2,"b":3}"#);
+
+    let second = rt.eval("({ b: 4 })").unwrap().into_object().unwrap();
+    target.assign(&[&first, &second]).unwrap();
+    assert_eq!(target.get("b").unwrap().as_number(), Some(4.0));
+    target.assign(&[]).unwrap();
+    assert_eq!(target.keys().unwrap(), ["a", "b"]);
+}
+
+#[test]
+fn set_many_writes_in_order() {
+    let rt = Runtime::new().unwrap();
+    let obj = Object::new(&rt).unwrap();
+    let keys: Vec<String> = (0..1000).map(|i| format!("key{}", i)).collect();
+    let pairs: Vec<(&str, Value)> = keys
+        .iter()
+        .enumerate()
+        .map(|(i, key)| (key.as_str(), Value::Number(i as f64)))
+        .collect();
+    obj.set_many(&pairs).unwrap();
+
+    assert_eq!(obj.keys().unwrap().len(), 1000);
+    for i in [0, 1, 499, 999] {
+        let value = obj.get(&format!("key{}", i)).unwrap();
+        assert_eq!(value.as_number(), Some(i as f64));
+    }
+
+    // Keys that need escaping, and more pairs than fit in one batch.
+    let odd = ["quote\"", "back\\slash", "new\nline", "", "ünï"];
+    let many: Vec<String> = (0..2500).map(|i| format!("k{}", i)).collect();
+    let pairs: Vec<(&str, Value)> = odd
+        .iter()
+        .copied()
+        .chain(many.iter().map(String::as_str))
+        .map(|key| (key, key.into_js(&rt).unwrap()))
+        .collect();
+    let wide = Object::new(&rt).unwrap();
+    wide.set_many(&pairs).unwrap();
+    for key in odd.iter().copied().chain(["k0", "k1024", "k2499"]) {
+        assert_eq!(String::from_js(&wide.get(key).unwrap()).unwrap(), key);
+    }
+
+    // Writes before a failing setter are kept.
+    let guarded = rt
+        .eval("({ set bad(v) { throw new Error('rejected'); } })")
+        .unwrap()
+        .into_object()
+        .unwrap();
+    let err = guarded
+        .set_many(&[
+            ("a", Value::Number(1.0)),
+            ("bad", Value::Number(2.0)),
+            ("c", Value::Number(3.0)),
+        ])
+        .unwrap_err();
+    assert_eq!(err.message(), "rejected");
+    assert!(guarded.has("a").unwrap());
+    assert!(!guarded.has("c").unwrap());
+}
+
+#[test]
+fn unique_id_is_per_object() {
+    let rt = Runtime::new().unwrap();
+    let a = Object::new(&rt).unwrap();
+    let b = Object::new(&rt).unwrap();
+    let id = a.unique_id().unwrap();
+    assert_eq!(a.clone().unique_id().unwrap(), id);
+    assert_ne!(b.unique_id().unwrap(), id);
+
+    rt.global().set("a", a.into()).unwrap();
+    let again = rt.global().get("a").unwrap().into_object().unwrap();
+    assert_eq!(again.unique_id().unwrap(), id);
+}
+
+#[test]
+fn value_keyed_access() {
+    let rt = Runtime::new().unwrap();
+    let arr = rt.eval("[10, 20, 30]").unwrap().into_object().unwrap();
+    let second = arr.get_with_value(&Value::from_number(1.0)).unwrap();
+    assert_eq!(second.as_number(), Some(20.0));
+    let by_string = arr.get_with_value(&"2".into_js(&rt).unwrap()).unwrap();
+    assert_eq!(by_string.as_number(), Some(30.0));
+    let length = arr.get_with_value(&"length".into_js(&rt).unwrap()).unwrap();
+    assert_eq!(length.as_number(), Some(3.0));
+
+    arr.set_with_value(&Value::from_number(0.0), Value::Number(5.0))
+        .unwrap();
+    assert_eq!(arr.get_index(0).unwrap().as_number(), Some(5.0));
+}
+
+#[test]
+fn get_as_converts_fields() {
+    let rt = Runtime::new().unwrap();
+    let obj = rt
+        .eval("({ x: 1, name: 'a' })")
+        .unwrap()
+        .into_object()
+        .unwrap();
+    let x: f64 = obj.get_as("x").unwrap();
+    let name: String = obj.get_as("name").unwrap();
+    assert_eq!((x, name.as_str()), (1.0, "a"));
+
+    let err = obj.get_as::<f64>("name").unwrap_err();
+    assert_eq!(err.message(), "at .name: expected number, got string");
+    let missing: Option<f64> = obj.get_as("missing").unwrap();
+    assert_eq!(missing, None);
+}
@@ -0,0 +1,201 @@
+use rusty_hermes::{
+    hermes_op, Args, Coerce, Error, FromJs, FromJsArg, Function, Object, Runtime, Value,
+};
+
+#[hermes_op]
+fn add(a: f64, b: f64) -> f64 {
+    a + b
+}
+
+#[hermes_op]
+fn area(w: Coerce<f64>, h: Coerce<f64>) -> f64 {
+    *w * *h
+}
+
+#[hermes_op]
+fn greet(name: Option<String>) -> String {
+    format!("hello, {}", name.as_deref().unwrap_or("stranger"))
+}
+
+#[hermes_op]
+fn sum(args: Args) -> rusty_hermes::Result<f64> {
+    (0..args.len()).map(|i| args.get::<f64>(i)).sum()
+}
+
+#[hermes_op]
+fn count(args: rusty_hermes::Args) -> f64 {
+    args.len() as f64
+}
+
+mod cli {
+    /// Unrelated to `rusty_hermes::Args`; converted like any other type.
+    #[derive(rusty_hermes::FromJs)]
+    pub struct Args {
+        pub verbose: bool,
+    }
+}
+
+#[hermes_op]
+fn verbosity(args: cli::Args, level: f64) -> f64 {
+    if args.verbose {
+        level
+    } else {
+        0.0
+    }
+}
+
+#[hermes_op]
+fn get_x(this: Object) -> rusty_hermes::Result<f64> {
+    f64::from_js(&this.get("x")?)
+}
+
+#[derive(FromJs)]
+struct Wrapper(i32);
+
+#[derive(FromJs)]
+struct Labeled<T>(T);
+
+#[hermes_op]
+fn unwrap(w: Wrapper, label: Labeled<String>) -> String {
+    format!("{}: {}", label.0, w.0)
+}
+
+#[hermes_op]
+fn find(k: String) -> Option<f64> {
+    match k.as_str() {
+        "one" => Some(1.0),
+        _ => None,
+    }
+}
+
+#[test]
+fn op_is_registered_under_its_name() {
+    let rt = Runtime::new().unwrap();
+    rt.register_op::<add>().unwrap();
+    assert_eq!(rt.eval("add(1, 2)").unwrap().as_number(), Some(3.0));
+    assert_eq!(rt.eval("add.length").unwrap().as_number(), Some(2.0));
+    assert_eq!(add(2.0, 3.0), 5.0);
+}
+
+#[test]
+fn coerce_accepts_numeric_strings() {
+    let rt = Runtime::new().unwrap();
+    rt.register_op::<area>().unwrap();
+    rt.register_op::<add>().unwrap();
+    assert_eq!(rt.eval("area('3', 4)").unwrap().as_number(), Some(12.0));
+
+    let caught = rt
+        .eval("try { area('wide', 4) } catch (e) { e.name }")
+        .unwrap();
+    assert_eq!(
+        caught.into_string().unwrap().to_rust_string().unwrap(),
+        "TypeError"
+    );
+    let caught = rt.eval("try { add('3', 4) } catch (e) { e.name }").unwrap();
+    assert_eq!(
+        caught.into_string().unwrap().to_rust_string().unwrap(),
+        "TypeError"
+    );
+}
+
+#[test]
+fn coerce_integers_reject_fractions_and_out_of_range() {
+    let rt = Runtime::new().unwrap();
+    let arg = |source: &str| rt.eval(source).unwrap();
+    assert_eq!(Coerce::<u8>::from_arg(&arg("'200'")).unwrap().0, 200);
+    assert_eq!(Coerce::<i32>::from_arg(&arg("-7")).unwrap().0, -7);
+    for source in ["'1.5'", "300", "-1", "Infinity", "'abc'"] {
+        assert!(
+            matches!(
+                Coerce::<u8>::from_arg(&arg(source)),
+                Err(Error::TypeError(_))
+            ),
+            "{}",
+            source
+        );
+    }
+    assert!(Coerce::<i64>::from_arg(&arg("2 ** 60")).is_err());
+    assert_eq!(Coerce::<f64>::from_arg(&arg("'1.5'")).unwrap().0, 1.5);
+}
+
+#[test]
+fn conversion_errors_name_the_argument() {
+    let rt = Runtime::new().unwrap();
+    rt.register_op::<add>().unwrap();
+    let message: String = rt
+        .eval_as("try { add(1) } catch (e) { e.message }")
+        .unwrap();
+    assert_eq!(message, "argument 1 ('b'): expected number, got undefined");
+}
+
+#[test]
+fn option_arguments_can_be_omitted() {
+    let rt = Runtime::new().unwrap();
+    rt.register_op::<greet>().unwrap();
+    let omitted: String = rt.eval_as("greet()").unwrap();
+    assert_eq!(omitted, "hello, stranger");
+    let given: String = rt.eval_as("greet('x')").unwrap();
+    assert_eq!(given, "hello, x");
+}
+
+#[test]
+fn args_op_is_variadic() {
+    let rt = Runtime::new().unwrap();
+    rt.register_op::<sum>().unwrap();
+    assert_eq!(rt.eval_as::<f64>("sum()").unwrap(), 0.0);
+    assert_eq!(rt.eval_as::<f64>("sum(1, 2, 3, 4.5)").unwrap(), 10.5);
+    assert_eq!(rt.eval_as::<f64>("sum.length").unwrap(), 0.0);
+}
+
+#[test]
+fn only_rusty_hermes_args_is_variadic() {
+    let rt = Runtime::new().unwrap();
+    rt.register_op::<count>().unwrap();
+    rt.register_op::<verbosity>().unwrap();
+    assert_eq!(rt.eval_as::<f64>("count(1, 2, 3)").unwrap(), 3.0);
+    assert_eq!(rt.eval_as::<f64>("count.length").unwrap(), 0.0);
+    assert_eq!(
+        rt.eval_as::<f64>("verbosity({ verbose: true }, 2)")
+            .unwrap(),
+        2.0
+    );
+    assert_eq!(rt.eval_as::<f64>("verbosity.length").unwrap(), 2.0);
+}
+
+#[test]
+fn this_param_binds_the_receiver() {
+    let rt = Runtime::new().unwrap();
+    let point = Object::new(&rt).unwrap();
+    point.set("x", Value::Number(4.0)).unwrap();
+    let func = Function::from_op::<get_x>(&rt).unwrap();
+    point.set("getX", func.clone().into()).unwrap();
+
+    let x = func.call_with_this(&point.clone().into(), &[]).unwrap();
+    assert_eq!(x.as_number(), Some(4.0));
+    assert!(func.call(&[]).is_err());
+
+    rt.global().set("point", point.into()).unwrap();
+    assert_eq!(rt.eval_as::<f64>("point.getX()").unwrap(), 4.0);
+    assert_eq!(rt.eval_as::<f64>("point.getX.length").unwrap(), 0.0);
+}
+
+#[test]
+fn derived_newtypes_are_op_args() {
+    let rt = Runtime::new().unwrap();
+    rt.register_op::<unwrap>().unwrap();
+    let text: String = rt.eval_as("unwrap(7, 'w')").unwrap();
+    assert_eq!(text, "w: 7");
+
+    let message: String = rt
+        .eval_as("try { unwrap('7', 'w') } catch (e) { e.message }")
+        .unwrap();
+    assert_eq!(message, "argument 0 ('w'): expected number, got string");
+}
+
+#[test]
+fn option_result_maps_none_to_null() {
+    let rt = Runtime::new().unwrap();
+    rt.register_op::<find>().unwrap();
+    assert_eq!(rt.eval("find('one')").unwrap().as_number(), Some(1.0));
+    assert!(rt.eval("find('two')").unwrap().is_null());
+}
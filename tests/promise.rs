@@ -0,0 +1,89 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use rusty_hermes::{Error, Runtime};
+
+#[test]
+fn await_fulfilled_promise() {
+    let rt = Runtime::new().unwrap();
+    let promise = rt
+        .eval("Promise.resolve(42)")
+        .unwrap()
+        .into_promise()
+        .unwrap();
+    assert_eq!(rt.await_promise(&promise).unwrap().as_number(), Some(42.0));
+
+    let chained = rt
+        .eval("Promise.resolve(1).then(x => x + 1).then(x => x * 10)")
+        .unwrap()
+        .into_promise()
+        .unwrap();
+    assert_eq!(rt.await_promise(&chained).unwrap().as_number(), Some(20.0));
+}
+
+#[test]
+fn await_rejected_promise() {
+    let rt = Runtime::new().unwrap();
+    let promise = rt
+        .eval("Promise.reject(new RangeError('nope'))")
+        .unwrap()
+        .into_promise()
+        .unwrap();
+    let err = rt.await_promise(&promise).unwrap_err();
+    assert_eq!(err.to_string(), "JavaScript exception: RangeError: nope");
+
+    let promise = rt
+        .eval("Promise.reject('plain')")
+        .unwrap()
+        .into_promise()
+        .unwrap();
+    assert_eq!(
+        rt.await_promise(&promise).unwrap_err(),
+        Error::JsException("plain".to_string())
+    );
+}
+
+#[test]
+fn pending_and_non_promises() {
+    let rt = Runtime::new().unwrap();
+    let pending = rt
+        .eval("new Promise(() => {})")
+        .unwrap()
+        .into_promise()
+        .unwrap();
+    assert!(matches!(
+        rt.await_promise(&pending),
+        Err(Error::RuntimeError(_))
+    ));
+    assert!(rt.eval("({ then() {} })").unwrap().into_promise().is_err());
+}
+
+#[test]
+fn rust_microtask_runs_once_when_drained() {
+    let rt = Runtime::new().unwrap();
+    let runs = Rc::new(Cell::new(0));
+    let counter = runs.clone();
+    rt.queue_rust_microtask(move || counter.set(counter.get() + 1))
+        .unwrap();
+    assert_eq!(runs.get(), 0);
+
+    rt.drain_microtasks().unwrap();
+    assert_eq!(runs.get(), 1);
+    rt.drain_microtasks().unwrap();
+    assert_eq!(runs.get(), 1);
+}
+
+#[test]
+fn queued_js_function_runs_after_the_turn() {
+    let rt = Runtime::new().unwrap();
+    let bump = rt
+        .eval("globalThis.count = 0; (function () { count += 1; })")
+        .unwrap()
+        .into_function()
+        .unwrap();
+    rt.queue_microtask(&bump).unwrap();
+    assert_eq!(rt.eval("count").unwrap().as_number(), Some(0.0));
+
+    rt.drain_microtasks().unwrap();
+    assert_eq!(rt.eval("count").unwrap().as_number(), Some(1.0));
+}
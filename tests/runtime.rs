@@ -0,0 +1,407 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use rusty_hermes::{Error, FromJs, Function, JsErrorKind, JsString, Runtime, Value};
+
+#[test]
+fn js_stack_depth_counts_nested_host_calls() {
+    let rt = Runtime::new().unwrap();
+    assert_eq!(rt.js_stack_depth(), 0);
+
+    let max_depth = Rc::new(Cell::new(0));
+    let seen = max_depth.clone();
+    let recurse = Function::from_host_fn(&rt, "recurse", 1, move |rt, _this, args| {
+        seen.set(seen.get().max(rt.js_stack_depth()));
+        let n = args[0].as_number().unwrap();
+        if n > 0.0 {
+            rt.eval(&format!("recurse({})", n - 1.0))?;
+        }
+        Ok(Value::Undefined)
+    })
+    .unwrap();
+    rt.global().set("recurse", recurse.into()).unwrap();
+
+    rt.eval("recurse(2)").unwrap();
+    assert_eq!(max_depth.get(), 3);
+    assert_eq!(rt.js_stack_depth(), 0);
+}
+
+#[test]
+fn throw_error_is_reported_by_next_call() {
+    let rt = Runtime::new().unwrap();
+    rt.throw_error(JsErrorKind::RangeError, "out of range")
+        .unwrap();
+
+    let err = rt.eval("1").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "JavaScript exception: RangeError: out of range"
+    );
+    assert_eq!(rt.eval("1").unwrap().as_number(), Some(1.0));
+}
+
+#[test]
+fn throw_error_survives_calls_that_are_not_eval_or_call() {
+    let rt = Runtime::new().unwrap();
+    let object = rt.eval("({ x: 1 })").unwrap().into_object().unwrap();
+    let text = JsString::new(&rt, "héllo").unwrap();
+    rt.throw_error(JsErrorKind::TypeError, "raised earlier")
+        .unwrap();
+
+    assert_eq!(object.get("x").unwrap().as_number(), Some(1.0));
+    assert_eq!(text.len_utf16(), 5);
+    assert!(rt.has_pending_exception());
+
+    let err = rt.eval("1").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "JavaScript exception: TypeError: raised earlier"
+    );
+    assert!(!rt.has_pending_exception());
+    assert_eq!(rt.eval("1").unwrap().as_number(), Some(1.0));
+}
+
+#[test]
+fn host_reentrancy_limit_stops_runaway_recursion() {
+    let rt = Runtime::new().unwrap();
+    let calls = Rc::new(Cell::new(0));
+    let counted = calls.clone();
+    let recurse = Function::from_host_fn(&rt, "recurse", 0, move |rt, _this, _args| {
+        counted.set(counted.get() + 1);
+        rt.eval("recurse()")
+    })
+    .unwrap();
+    rt.global().set("recurse", recurse.into()).unwrap();
+    rt.set_host_reentrancy_limit(Some(3));
+
+    // Each level rethrows the inner error, so only the end is fixed.
+    let err = rt.eval("recurse()").unwrap_err();
+    assert!(err.message().ends_with("host reentrancy limit exceeded"));
+    assert_eq!(calls.get(), 3);
+    assert_eq!(rt.js_stack_depth(), 0);
+}
+
+#[test]
+fn throw_error_inside_host_function_throws_into_js() {
+    let rt = Runtime::new().unwrap();
+    let fail = Function::from_host_fn(&rt, "fail", 0, |rt, _this, _args| {
+        rt.throw_error(JsErrorKind::TypeError, "nope")?;
+        Ok(Value::Undefined)
+    })
+    .unwrap();
+    rt.global().set("fail", fail.into()).unwrap();
+
+    let caught = rt
+        .eval("try { fail(); 'no error' } catch (e) { e instanceof TypeError && e.message }")
+        .unwrap();
+    assert_eq!(
+        caught.into_string().unwrap().to_rust_string().unwrap(),
+        "nope"
+    );
+}
+
+#[test]
+fn pending_exception_can_be_taken_back() {
+    let rt = Runtime::new().unwrap();
+    let recover = Function::from_host_fn(&rt, "recover", 0, |rt, _this, _args| {
+        rt.throw_error(JsErrorKind::RangeError, "too big")?;
+        assert!(rt.has_pending_exception());
+        let err = rt.take_pending_exception().expect("pending error");
+        assert!(!rt.has_pending_exception());
+        Ok(JsString::new(rt, &format!("recovered from {}", err.message()))?.into())
+    })
+    .unwrap();
+    rt.global().set("recover", recover.into()).unwrap();
+
+    let result: String = rt.eval_as("recover()").unwrap();
+    assert_eq!(result, "recovered from too big");
+    assert!(rt.take_pending_exception().is_none());
+}
+
+#[test]
+fn global_shortcuts() {
+    let rt = Runtime::new().unwrap();
+    rt.set_global("x", Value::Number(42.0)).unwrap();
+    assert_eq!(rt.eval("x").unwrap().as_number(), Some(42.0));
+
+    rt.eval("var y = 'set in js'").unwrap();
+    let y: String = String::from_js(&rt.get_global("y").unwrap()).unwrap();
+    assert_eq!(y, "set in js");
+    assert!(rt.get_global("missing").unwrap().is_undefined());
+}
+
+#[test]
+fn eval_uses_default_url() {
+    let rt = Runtime::new().unwrap();
+    rt.set_default_eval_url("my-app.js");
+    let stack = rt.eval("new Error('x').stack").unwrap();
+    let stack = stack.into_string().unwrap().to_rust_string().unwrap();
+    assert!(stack.contains("my-app.js"), "{}", stack);
+}
+
+#[test]
+fn active_features_match_globals() {
+    let rt = Runtime::new().unwrap();
+    let features = rt.active_features();
+    assert!(features.eval);
+    assert!(features.generators);
+    assert!(features.promise);
+    let has_proxy = rt.eval("typeof Proxy === 'function'").unwrap();
+    assert_eq!(Some(features.proxy), has_proxy.as_bool());
+    let has_intl = rt.eval("typeof Intl === 'object'").unwrap();
+    assert_eq!(Some(features.intl), has_intl.as_bool());
+}
+
+#[test]
+fn hermes_internal_exposes_diagnostics() {
+    let rt = Runtime::new().unwrap();
+    let internal = rt.hermes_internal().unwrap();
+    let stats = internal.get("getInstrumentedStats").unwrap();
+    let stats = stats.into_function().unwrap().call(&[]).unwrap();
+    assert!(stats.is_object());
+}
+
+#[test]
+fn collect_garbage_frees_unreachable_objects() {
+    let rt = Runtime::new().unwrap();
+    rt.eval("globalThis.big = new Array(1000000).fill(0).map((_, i) => ({ i }))")
+        .unwrap();
+    rt.collect_garbage().unwrap();
+    let before = rt.heap_info().unwrap();
+
+    rt.eval("big = null").unwrap();
+    rt.collect_garbage().unwrap();
+    let after = rt.heap_info().unwrap();
+    assert!(after.allocated_bytes < before.allocated_bytes);
+    assert!(after.num_collections > before.num_collections);
+}
+
+#[test]
+fn gc_stats_accumulate() {
+    let rt = Runtime::new().unwrap();
+    let before = rt.gc_stats().unwrap();
+    rt.eval("for (let i = 0; i < 1000; i++) new Array(1000).fill({})")
+        .unwrap();
+    rt.collect_garbage().unwrap();
+    let after = rt.gc_stats().unwrap();
+    assert!(after.count > before.count);
+    assert!(after.total_pause >= before.total_pause);
+}
+
+#[derive(Debug, PartialEq, rusty_hermes::FromJs)]
+struct Point {
+    x: f64,
+    y: f64,
+}
+
+#[test]
+fn eval_as_converts_result() {
+    let rt = Runtime::new().unwrap();
+    let n: f64 = rt.eval_as("1 + 2").unwrap();
+    assert_eq!(n, 3.0);
+    let s: String = rt.eval_as("'hi'").unwrap();
+    assert_eq!(s, "hi");
+    let v: Vec<i32> = rt.eval_as("[1, 2, 3]").unwrap();
+    assert_eq!(v, vec![1, 2, 3]);
+    let p: Point = rt.eval_as("({ x: 1, y: -1 })").unwrap();
+    assert_eq!(p, Point { x: 1.0, y: -1.0 });
+    assert!(matches!(
+        rt.eval_as::<String>("1"),
+        Err(Error::TypeError(_))
+    ));
+}
+
+#[test]
+fn check_syntax_does_not_run_code() {
+    let rt = Runtime::new().unwrap();
+    rt.check_syntax("globalThis.ran = true;", "ok.js").unwrap();
+    let ran: String = rt.eval_as("typeof ran").unwrap();
+    assert_eq!(ran, "undefined");
+
+    let err = rt.check_syntax("let x = ;", "bad.js").unwrap_err();
+    match err {
+        Error::JsError { name, message, .. } => {
+            assert_eq!(name, "SyntaxError");
+            assert!(message.starts_with("bad.js: "), "{}", message);
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+
+    // The body cannot close the wrapper early.
+    assert!(rt
+        .check_syntax("}); globalThis.ran = true; (function () {", "x.js")
+        .is_err());
+}
+
+#[test]
+fn preload_scripts_checks_all_up_front() {
+    let rt = Runtime::new().unwrap();
+    let scripts = rt
+        .preload_scripts(&[("a.js", "var a = 1;"), ("b.js", "a + 1")])
+        .unwrap();
+    assert_eq!(scripts[1].url(), "b.js");
+    let ran: String = rt.eval_as("typeof a").unwrap();
+    assert_eq!(ran, "undefined");
+
+    rt.evaluate_prepared(&scripts[0]).unwrap();
+    let b = rt.evaluate_prepared(&scripts[1]).unwrap();
+    assert_eq!(b.as_number(), Some(2.0));
+
+    let err = rt
+        .preload_scripts(&[("ok.js", "1"), ("bad.js", "let x = ;")])
+        .unwrap_err();
+    assert!(err.message().starts_with("bad.js: "), "{}", err);
+}
+
+#[test]
+fn restore_global_undoes_changes() {
+    let rt = Runtime::new().unwrap();
+    rt.eval("globalThis.kept = 1").unwrap();
+    let snapshot = rt.global_snapshot().unwrap();
+
+    rt.eval("globalThis.added = 2; kept = 3; Math = null; var declared = 4")
+        .unwrap();
+    rt.restore_global(&snapshot).unwrap();
+
+    let state: String = rt
+        .eval_as("[typeof added, kept, typeof Math.max, typeof declared].join()")
+        .unwrap();
+    assert_eq!(state, "undefined,1,function,undefined");
+}
+
+#[test]
+fn define_constant_is_read_only() {
+    let rt = Runtime::new().unwrap();
+    rt.define_constant("VERSION", Value::Number(3.0)).unwrap();
+
+    let state: String = rt
+        .eval_as("VERSION = 4; delete globalThis.VERSION; [VERSION, Object.keys(globalThis).includes('VERSION')].join()")
+        .unwrap();
+    assert_eq!(state, "3,false");
+    assert!(rt.eval("'use strict'; VERSION = 4").is_err());
+}
+
+#[test]
+fn prepare_callable_compiles_once() {
+    let rt = Runtime::new().unwrap();
+    let rule = rt
+        .prepare_callable(&["ctx", "limit"], "return ctx.total > limit;")
+        .unwrap();
+    assert_eq!(
+        rule.call(&[Value::Number(1.0)]).unwrap().as_bool(),
+        Some(false)
+    );
+    for (total, expected) in [(5.0, true), (2.0, false)] {
+        let ctx = rt.eval(&format!("({{ total: {} }})", total)).unwrap();
+        let result = rule.call(&[ctx, Value::Number(3.0)]).unwrap();
+        assert_eq!(result.as_bool(), Some(expected));
+    }
+    assert!(rt.prepare_callable(&["a"], "return a +;").is_err());
+}
+
+#[test]
+fn thrown_error_stacks_name_the_source_url() {
+    let rt = Runtime::new().unwrap();
+    let err = rt
+        .eval_named(
+            "function boot() { throw new Error('nope'); }\nboot();",
+            "app.js",
+        )
+        .unwrap_err();
+    match err {
+        Error::JsError {
+            stack: Some(stack), ..
+        } => assert!(stack.contains("app.js"), "{}", stack),
+        other => panic!("expected a stack, got {:?}", other),
+    }
+
+    rt.set_default_eval_url("default.js");
+    match rt.eval("throw new Error('x')").unwrap_err() {
+        Error::JsError {
+            stack: Some(stack), ..
+        } => assert!(stack.contains("default.js"), "{}", stack),
+        other => panic!("expected a stack, got {:?}", other),
+    }
+}
+
+#[test]
+fn thrown_errors_keep_name_and_stack() {
+    let rt = Runtime::new().unwrap();
+    let err = rt
+        .eval("function fail() { throw new TypeError('oops'); } fail()")
+        .unwrap_err();
+    match err {
+        Error::JsError {
+            name,
+            message,
+            stack,
+        } => {
+            assert_eq!(name, "TypeError");
+            assert_eq!(message, "oops");
+            assert!(stack.is_some_and(|stack| stack.contains("fail")));
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+
+    let err = rt.eval("throw 42").unwrap_err();
+    assert_eq!(err, Error::JsException("42".to_string()));
+}
+
+#[test]
+fn evaluate_bytecode_rejects_source() {
+    let rt = Runtime::new().unwrap();
+    let source = b"1 + 2";
+    assert!(!Runtime::is_hermes_bytecode(source));
+
+    let err = rt.evaluate_bytecode(source, "app.hbc").unwrap_err();
+    assert!(matches!(err, Error::RuntimeError(_)));
+    assert_eq!(err.message(), "app.hbc: not Hermes bytecode");
+}
+
+#[test]
+fn eval_bytes_treats_non_bytecode_as_source() {
+    let rt = Runtime::new().unwrap();
+    let sum = rt.eval_bytes(b"1 + 2", "sum.js").unwrap();
+    assert_eq!(sum.as_number(), Some(3.0));
+
+    let err = rt.eval_bytes(b"'\xff'", "bad.js").unwrap_err();
+    assert!(matches!(err, Error::RuntimeError(_)));
+    assert!(
+        err.message().starts_with("bad.js: invalid UTF-8"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn eval_file_reads_and_evaluates_the_script() {
+    let rt = Runtime::new().unwrap();
+    let path =
+        std::env::temp_dir().join(format!("rusty_hermes_eval_file_{}.js", std::process::id()));
+    std::fs::write(&path, "1+2").unwrap();
+    let sum = rt.eval_file(&path);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(sum.unwrap().as_number(), Some(3.0));
+
+    let err = rt.eval_file(&path).unwrap_err();
+    match err {
+        Error::Io(err) => assert_eq!(err.kind(), std::io::ErrorKind::NotFound),
+        other => panic!("expected an io error, got {:?}", other),
+    }
+}
+
+#[test]
+fn io_errors_convert_and_keep_their_source() {
+    use std::error::Error as _;
+
+    fn open() -> rusty_hermes::Result<std::fs::File> {
+        Ok(std::fs::File::open("/nonexistent/rusty_hermes/script.js")?)
+    }
+    let err = open().unwrap_err();
+    assert!(matches!(&err, Error::Io(io) if io.kind() == std::io::ErrorKind::NotFound));
+    let source = err.source().expect("io errors have a source");
+    assert!(source.downcast_ref::<std::io::Error>().is_some());
+    assert_eq!(err.clone(), err);
+
+    assert!(Error::TypeError("x".into()).source().is_none());
+}
@@ -0,0 +1,89 @@
+#![cfg(feature = "serde")]
+
+use std::collections::BTreeMap;
+
+use rusty_hermes::{from_value, to_value, Runtime};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Profile {
+    user_name: String,
+    login_count: u32,
+    nick_name: Option<String>,
+    tags: Vec<String>,
+    scores: BTreeMap<String, f64>,
+    shape: Shape,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Shape {
+    Point,
+    Circle(f64),
+    Rect { w: f64, h: f64 },
+    Line(f64, f64),
+}
+
+#[test]
+fn camel_case_struct_round_trips_through_a_js_object() {
+    let rt = Runtime::new().unwrap();
+    let profile = Profile {
+        user_name: "ada".into(),
+        login_count: 3,
+        nick_name: None,
+        tags: vec!["a".into(), "b".into()],
+        scores: BTreeMap::from([("x".into(), 1.5)]),
+        shape: Shape::Rect { w: 2.0, h: 3.0 },
+    };
+    rt.global()
+        .set("profile", to_value(&rt, &profile).unwrap())
+        .unwrap();
+
+    let seen: String = rt
+        .eval_as("[profile.userName, profile.loginCount, profile.nickName, profile.tags.join('+'), profile.scores.x, profile.shape.Rect.h].join()")
+        .unwrap();
+    assert_eq!(seen, "ada,3,,a+b,1.5,3");
+
+    let back: Profile = from_value(&rt.eval("profile").unwrap()).unwrap();
+    assert_eq!(back, profile);
+
+    let edited: Profile = from_value(
+        &rt.eval("({ userName: 'bo', loginCount: 0, nickName: 'b', tags: [], scores: {}, shape: 'Point' })")
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(edited.nick_name.as_deref(), Some("b"));
+    assert_eq!(edited.shape, Shape::Point);
+}
+
+#[test]
+fn enums_use_external_tagging() {
+    let rt = Runtime::new().unwrap();
+    for shape in [
+        Shape::Point,
+        Shape::Circle(1.0),
+        Shape::Rect { w: 1.0, h: 2.0 },
+        Shape::Line(0.5, 4.0),
+    ] {
+        let value = to_value(&rt, &shape).unwrap();
+        assert_eq!(from_value::<Shape>(&value).unwrap(), shape);
+    }
+    let json = to_value(&rt, &Shape::Line(0.5, 4.0))
+        .unwrap()
+        .to_json_string(None)
+        .unwrap();
+    assert_eq!(json, r#"{"Line":[0.5,4]}"#);
+}
+
+#[test]
+fn deserialize_errors_carry_the_path() {
+    let rt = Runtime::new().unwrap();
+    let value = rt
+        .eval("({ userName: 'x', loginCount: 1, tags: ['a', 2], scores: {}, shape: 'Point' })")
+        .unwrap();
+    let err = from_value::<Profile>(&value).unwrap_err();
+    assert!(err.message().starts_with("at .tags[1]: "), "{}", err);
+
+    let value = rt.eval("({ Square: 1 })").unwrap();
+    assert!(from_value::<Shape>(&value).is_err());
+}
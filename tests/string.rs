@@ -0,0 +1,111 @@
+use std::cmp::Ordering;
+
+use rusty_hermes::{Error, JsString, JsStringBuilder, Runtime};
+
+#[test]
+fn lengths_count_bytes_and_code_units() {
+    let rt = Runtime::new().unwrap();
+    let accented = JsString::new(&rt, "héllo").unwrap();
+    assert_eq!(accented.len_utf8(), 6);
+    assert_eq!(accented.len_utf16(), 5);
+
+    let emoji = rt.eval("'a😀'").unwrap().into_string().unwrap();
+    assert_eq!(emoji.len_utf8(), 5);
+    assert_eq!(emoji.len_utf16(), 3);
+    assert!(!emoji.is_empty());
+
+    assert!(JsString::new(&rt, "").unwrap().is_empty());
+}
+
+#[test]
+fn builder_joins_pieces() {
+    let rt = Runtime::new().unwrap();
+    let mut builder = JsStringBuilder::new(&rt).unwrap();
+    builder.push_str("hello, ").unwrap();
+    builder.push_str("").unwrap();
+    builder.push_str("wörld").unwrap();
+    assert_eq!(
+        builder.finish().unwrap().to_rust_string().unwrap(),
+        "hello, wörld"
+    );
+}
+
+#[test]
+fn create_string_from_reader_handles_split_characters() {
+    let rt = Runtime::new().unwrap();
+    // Long enough to span several chunks, with multi-byte characters
+    // straddling the chunk boundaries.
+    let text = "aλ😀".repeat(50_000);
+    let s = rt.create_string_from_reader(text.as_bytes()).unwrap();
+    assert_eq!(s.len_utf8(), text.len());
+    assert_eq!(s.to_rust_string().unwrap(), text);
+
+    let err = rt
+        .create_string_from_reader(&[0x61, 0xF0, 0x9F][..])
+        .unwrap_err();
+    assert!(matches!(err, Error::RuntimeError(_)));
+}
+
+#[test]
+fn create_string_from_reader_keeps_io_errors() {
+    struct Failing;
+
+    impl std::io::Read for Failing {
+        fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::new(
+                std::io::ErrorKind::ConnectionReset,
+                "peer went away",
+            ))
+        }
+    }
+
+    let rt = Runtime::new().unwrap();
+    match rt.create_string_from_reader(Failing).unwrap_err() {
+        Error::Io(err) => {
+            assert_eq!(err.kind(), std::io::ErrorKind::ConnectionReset);
+            assert_eq!(err.to_string(), "peer went away");
+        }
+        other => panic!("expected an io error, got {:?}", other),
+    }
+}
+
+#[test]
+fn from_utf8_bytes() {
+    let rt = Runtime::new().unwrap();
+    let s = JsString::from_utf8(&rt, "héllo".as_bytes()).unwrap();
+    assert_eq!(s.to_rust_string().unwrap(), "héllo");
+
+    let err = JsString::from_utf8(&rt, b"ab\xff").unwrap_err();
+    assert!(matches!(err, Error::TypeError(_)));
+
+    let known_good = b"from the network";
+    let s = unsafe { JsString::from_utf8_unchecked(&rt, known_good) }.unwrap();
+    assert_eq!(s.to_rust_string().unwrap(), "from the network");
+}
+
+#[test]
+fn concat_compare_and_affixes() {
+    let rt = Runtime::new().unwrap();
+    let apple = JsString::new(&rt, "apple").unwrap();
+    let banana = JsString::new(&rt, "banana").unwrap();
+
+    let both = apple.concat(&banana).unwrap();
+    assert_eq!(both.to_rust_string().unwrap(), "applebanana");
+    assert!(both.starts_with(&apple).unwrap());
+    assert!(both.ends_with(&banana).unwrap());
+    assert!(!both.starts_with(&banana).unwrap());
+
+    assert_eq!(apple.compare(&banana).unwrap(), Ordering::Less);
+    assert_eq!(banana.compare(&apple).unwrap(), Ordering::Greater);
+    assert_eq!(
+        apple
+            .compare(&JsString::new(&rt, "apple").unwrap())
+            .unwrap(),
+        Ordering::Equal
+    );
+
+    // U+FF5E sorts after U+1F600 by UTF-16 code units, before by code points.
+    let wide = JsString::new(&rt, "\u{FF5E}").unwrap();
+    let emoji = JsString::new(&rt, "\u{1F600}").unwrap();
+    assert_eq!(wide.compare(&emoji).unwrap(), Ordering::Greater);
+}
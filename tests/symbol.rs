@@ -0,0 +1,50 @@
+use rusty_hermes::{Object, PropNameId, Runtime, Symbol, Value, WellKnown};
+
+#[test]
+fn new_symbols_keep_their_description() {
+    let rt = Runtime::new().unwrap();
+    let tagged = Symbol::new(&rt, Some("tag")).unwrap();
+    assert_eq!(tagged.description().unwrap().as_deref(), Some("tag"));
+    assert_eq!(tagged.to_rust_string().unwrap(), "Symbol(tag)");
+
+    let bare = Symbol::new(&rt, None).unwrap();
+    assert_eq!(bare.description().unwrap(), None);
+
+    rt.global().set("tagged", tagged.into()).unwrap();
+    let unique: bool = rt.eval_as("tagged !== Symbol('tag')").unwrap();
+    assert!(unique);
+}
+
+#[test]
+fn registry_symbols_are_shared() {
+    let rt = Runtime::new().unwrap();
+    let key = Symbol::for_key(&rt, "app.key").unwrap();
+    rt.global().set("key", key.into()).unwrap();
+    let shared: bool = rt.eval_as("key === Symbol.for('app.key')").unwrap();
+    assert!(shared);
+}
+
+#[test]
+fn well_known_iterator_indexes_arrays() {
+    let rt = Runtime::new().unwrap();
+    let array: Object = rt.eval("[10, 20]").unwrap().into_object().unwrap();
+    let iterator = Symbol::well_known(&rt, WellKnown::Iterator).unwrap();
+
+    let values = array
+        .get_with_propnameid(&PropNameId::from_symbol(&iterator).unwrap())
+        .unwrap()
+        .into_function()
+        .unwrap();
+    let iter = values
+        .call_with_this(&array.into(), &[])
+        .unwrap()
+        .into_object()
+        .unwrap();
+    let first = iter
+        .call_method("next", &[])
+        .unwrap()
+        .into_object()
+        .unwrap();
+    assert_eq!(first.get("value").unwrap().as_number(), Some(10.0));
+    assert!(matches!(first.get("done").unwrap(), Value::Bool(false)));
+}
@@ -0,0 +1,143 @@
+use rusty_hermes::{ArrayBuffer, Error, Float64Array, Runtime, TypedArrayKind};
+
+#[test]
+fn reads_float64_array_as_slice() {
+    let rt = Runtime::new().unwrap();
+    let value = rt.eval("new Float64Array([1.5, 2.5, -3])").unwrap();
+    let array = value.as_typed_array().unwrap();
+    assert_eq!(array.kind(), TypedArrayKind::Float64);
+    assert_eq!(array.len(), 3);
+    assert_eq!(array.to_vec::<f64>().unwrap(), [1.5, 2.5, -3.0]);
+    assert!(matches!(array.to_vec::<f32>(), Err(Error::TypeError(_))));
+    // SAFETY: no JS runs and no other view is used while the slice is alive.
+    assert_eq!(
+        unsafe { array.as_slice::<f64>() }.unwrap(),
+        &[1.5, 2.5, -3.0]
+    );
+}
+
+#[test]
+fn respects_byte_offset_of_subarrays() {
+    let rt = Runtime::new().unwrap();
+    let value = rt
+        .eval("new Uint8Array([1, 2, 3, 4]).subarray(1, 3)")
+        .unwrap();
+    let array = value.as_typed_array().unwrap();
+    assert_eq!(array.to_vec::<u8>().unwrap(), [2, 3]);
+
+    let value = rt.eval("new Uint8ClampedArray([7])").unwrap();
+    assert_eq!(value.as_typed_array().unwrap().to_vec::<u8>().unwrap(), [7]);
+}
+
+#[test]
+fn other_values_are_not_typed_arrays() {
+    let rt = Runtime::new().unwrap();
+    for source in [
+        "[1, 2]",
+        "new DataView(new ArrayBuffer(4))",
+        "new ArrayBuffer(4)",
+        "1",
+    ] {
+        assert!(
+            rt.eval(source).unwrap().as_typed_array().is_none(),
+            "{}",
+            source
+        );
+    }
+}
+
+#[test]
+fn float64_array_is_shared_with_js() {
+    let rt = Runtime::new().unwrap();
+    let mut array = Float64Array::new(&rt, 3).unwrap();
+    array.copy_from_slice(&[1.0, 2.0, 3.0]).unwrap();
+    rt.global().set("samples", array.clone().into()).unwrap();
+
+    rt.eval("samples[1] *= 10").unwrap();
+    assert_eq!(array.to_vec().unwrap(), [1.0, 20.0, 3.0]);
+    let mut head = [0.0; 2];
+    assert_eq!(array.copy_to_slice(&mut head).unwrap(), 2);
+    assert_eq!(head, [1.0, 20.0]);
+    assert!(matches!(
+        array.copy_from_slice(&[1.0]),
+        Err(Error::TypeError(_))
+    ));
+    // SAFETY: no JS runs and no other view is used while the slice is alive.
+    unsafe { array.as_mut_slice() }.unwrap()[0] = 5.0;
+    assert_eq!(rt.eval("samples[0]").unwrap().as_number(), Some(5.0));
+    assert_eq!(array.byte_offset().unwrap(), 0);
+    assert_eq!(array.buffer().unwrap().len().unwrap(), 24);
+}
+
+#[test]
+fn into_typed_array_checks_element_kind() {
+    let rt = Runtime::new().unwrap();
+    let value = rt.eval("new Int32Array([1, 2, 3]).subarray(1)").unwrap();
+    let array = value.clone().into_typed_array::<i32>().unwrap();
+    assert_eq!(array.kind(), TypedArrayKind::Int32);
+    assert_eq!(array.to_vec().unwrap(), [2, 3]);
+    assert_eq!(array.byte_offset().unwrap(), 4);
+    assert!(matches!(
+        value.into_typed_array::<f64>(),
+        Err(Error::TypeError(_))
+    ));
+    assert!(rt.eval("[1]").unwrap().into_typed_array::<f64>().is_err());
+}
+
+#[test]
+fn array_buffer_bytes() {
+    let rt = Runtime::new().unwrap();
+    let mut buffer = ArrayBuffer::new(&rt, 4).unwrap();
+    buffer.copy_from_slice(&[1, 2, 3, 4]).unwrap();
+    rt.global().set("buf", buffer.into()).unwrap();
+    let sum = rt
+        .eval("new Uint8Array(buf).reduce((a, b) => a + b)")
+        .unwrap();
+    assert_eq!(sum.as_number(), Some(10.0));
+}
+
+#[test]
+fn array_buffer_from_slice() {
+    let rt = Runtime::new().unwrap();
+    let mut buffer = ArrayBuffer::from_slice(&rt, &[1, 2, 3, 4]).unwrap();
+    rt.global().set("buf", buffer.clone().into()).unwrap();
+    let elements: Vec<u8> = rt.eval_as("Array.from(new Uint8Array(buf))").unwrap();
+    assert_eq!(elements, [1, 2, 3, 4]);
+
+    buffer.copy_from_slice(&[5, 6, 7, 8]).unwrap();
+    assert_eq!(
+        rt.eval("new Uint8Array(buf)[3]").unwrap().as_number(),
+        Some(8.0)
+    );
+    assert!(matches!(
+        buffer.copy_from_slice(&[1, 2]),
+        Err(Error::TypeError(_))
+    ));
+
+    rt.eval("new Uint8Array(buf)[0] = 9").unwrap();
+    assert_eq!(buffer.to_vec().unwrap(), [9, 6, 7, 8]);
+}
+
+#[test]
+fn array_buffer_copy_to_slice() {
+    let rt = Runtime::new().unwrap();
+    let buffer = ArrayBuffer::from_slice(&rt, &[1, 2, 3, 4]).unwrap();
+
+    let mut frame = [0u8; 6];
+    assert_eq!(buffer.copy_to_slice(&mut frame).unwrap(), 4);
+    assert_eq!(frame, [1, 2, 3, 4, 0, 0]);
+
+    let mut short = [0u8; 2];
+    assert_eq!(buffer.copy_to_slice(&mut short).unwrap(), 2);
+    assert_eq!(short, [1, 2]);
+
+    let mut exact = [0u8; 4];
+    buffer.copy_to_slice_exact(&mut exact).unwrap();
+    assert_eq!(exact, [1, 2, 3, 4]);
+    for len in [2, 6] {
+        let mut dst = vec![9u8; len];
+        let err = buffer.copy_to_slice_exact(&mut dst).unwrap_err();
+        assert!(matches!(err, Error::RuntimeError(_)), "{}", err);
+        assert!(dst.iter().all(|&b| b == 9));
+    }
+}
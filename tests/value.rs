@@ -0,0 +1,242 @@
+use rusty_hermes::{Array, Error, Global, Object, Runtime, Value};
+
+#[test]
+fn type_of_matches_js() {
+    let rt = Runtime::new().unwrap();
+    for source in [
+        "undefined",
+        "null",
+        "true",
+        "1.5",
+        "'s'",
+        "Symbol('s')",
+        "10n",
+        "({})",
+        "[]",
+        "(function () {})",
+        "Math.max",
+    ] {
+        let value = rt.eval(source).unwrap();
+        let expected = rt.eval(&format!("typeof ({})", source)).unwrap();
+        let expected = expected.into_string().unwrap().to_rust_string().unwrap();
+        assert_eq!(value.type_of(), expected, "typeof {}", source);
+    }
+    assert_eq!(rt.eval("null").unwrap().type_of(), "object");
+    assert_eq!(rt.eval("Math.max").unwrap().type_of(), "function");
+}
+
+#[test]
+fn to_json_string_uses_json_stringify() {
+    let rt = Runtime::new().unwrap();
+    let value = rt.eval("({ a: 1, b: [2, 3] })").unwrap();
+    assert_eq!(value.to_json_string(None).unwrap(), r#"{"a":1,"b":[2,3]}"#);
+    assert_eq!(
+        value.to_json_string(Some(2)).unwrap(),
+        "{\n  \"a\": 1,\n  \"b\": [\n    2,\n    3\n  ]\n}"
+    );
+    assert_eq!(
+        Value::from_number(1e21).to_json_string(None).unwrap(),
+        "1e+21"
+    );
+    assert!(matches!(
+        Value::undefined().to_json_string(None),
+        Err(Error::TypeError(_))
+    ));
+
+    let circular = rt.eval("var o = {}; o.self = o; o").unwrap();
+    assert!(matches!(
+        circular.to_json_string(None),
+        Err(Error::JsError { .. })
+    ));
+}
+
+#[test]
+fn display_matches_js_string() {
+    let rt = Runtime::new().unwrap();
+    for source in [
+        "undefined",
+        "null",
+        "true",
+        "0.1",
+        "-1e-7",
+        "123456789012",
+        "'hi'",
+        "[1, [2]]",
+        "10n",
+    ] {
+        let value = rt.eval(source).unwrap();
+        let expected = rt.eval(&format!("String({})", source)).unwrap();
+        let expected = expected.into_string().unwrap().to_rust_string().unwrap();
+        assert_eq!(value.to_string(), expected, "String({})", source);
+    }
+}
+
+#[test]
+fn bigint_to_f64_lossy() {
+    let rt = Runtime::new().unwrap();
+    let small = rt.eval("123n").unwrap().into_bigint().unwrap();
+    assert_eq!(small.to_f64_lossy().unwrap(), 123.0);
+    let huge = rt.eval("2n ** 64n + 1n").unwrap().into_bigint().unwrap();
+    assert_eq!(huge.to_f64_lossy().unwrap(), 18446744073709551616.0);
+}
+
+#[test]
+fn bigint_to_f64_lossy_rejects_a_replaced_number() {
+    let rt = Runtime::new().unwrap();
+    let big = rt
+        .eval("Number = () => 'x'; 5n")
+        .unwrap()
+        .into_bigint()
+        .unwrap();
+    let err = big.to_f64_lossy().unwrap_err();
+    assert!(matches!(err, Error::TypeError(_)), "{}", err);
+}
+
+#[test]
+fn instance_of_follows_the_prototype_chain() {
+    let rt = Runtime::new().unwrap();
+    let err = rt.eval("new TypeError('x')").unwrap();
+    let type_error = rt.global().get("TypeError").unwrap();
+    let error = rt.global().get("Error").unwrap();
+    let range_error = rt.global().get("RangeError").unwrap();
+    assert!(err.instance_of(&type_error).unwrap());
+    assert!(err.instance_of(&error).unwrap());
+    assert!(!err.instance_of(&range_error).unwrap());
+
+    assert!(!Value::Number(1.0).instance_of(&error).unwrap());
+    assert!(err.instance_of(&Value::Number(1.0)).is_err());
+}
+
+#[test]
+fn identity_hint_is_stable_per_object() {
+    let rt = Runtime::new().unwrap();
+    let obj = rt.eval("globalThis.o = {}; o").unwrap();
+    let again = rt.eval("o").unwrap();
+    let other = rt.eval("[]").unwrap();
+    assert!(obj.identity_hint().starts_with("Object#"));
+    assert_eq!(obj.identity_hint(), again.identity_hint());
+    assert!(other.identity_hint().starts_with("Array#"));
+    assert_ne!(
+        obj.identity_hint(),
+        rt.eval("({})").unwrap().identity_hint()
+    );
+
+    assert_eq!(Value::Number(1.5).identity_hint(), "1.5");
+    assert_eq!(rt.eval("'hi'").unwrap().identity_hint(), "\"hi\"");
+    assert_eq!(rt.eval("10n").unwrap().identity_hint(), "10n");
+    assert_eq!(rt.eval("Symbol('s')").unwrap().identity_hint(), "Symbol(s)");
+}
+
+struct Cache {
+    greeting: Global,
+    count: Global,
+}
+
+#[test]
+fn global_handles_outlive_scopes() {
+    let rt = Runtime::new().unwrap();
+    let cache = {
+        let greeting = rt.eval("'hello' + ' world'").unwrap();
+        Cache {
+            greeting: Global::new(&greeting),
+            count: Global::new(&Value::Number(2.0)),
+        }
+    };
+
+    for _ in 0..3 {
+        rt.eval("var garbage = []; for (var i = 0; i < 1000; i++) garbage.push({ i: i });")
+            .unwrap();
+    }
+    rt.collect_garbage().unwrap();
+
+    let greeting = cache.greeting.get(&rt).unwrap();
+    assert_eq!(greeting.to_string(), "hello world");
+    assert_eq!(cache.count.get(&rt).unwrap().as_number(), Some(2.0));
+
+    let other = Runtime::new().unwrap();
+    assert!(cache.greeting.get(&other).is_err());
+    // Primitives are held inline and belong to no runtime.
+    assert!(cache.count.get(&other).is_ok());
+}
+
+#[test]
+fn loose_and_strict_equality() {
+    let rt = Runtime::new().unwrap();
+    let one_str = rt.eval("'1'").unwrap();
+    let one = Value::Number(1.0);
+    assert!(one_str.loose_equals(&one).unwrap());
+    assert!(!one_str.strict_equals(&one));
+
+    let nan = Value::Number(f64::NAN);
+    assert!(!nan.strict_equals(&nan));
+    assert!(!nan.loose_equals(&nan).unwrap());
+
+    assert!(Value::Null.loose_equals(&Value::Undefined).unwrap());
+    assert!(!Value::Null.strict_equals(&Value::Undefined));
+    assert!(Value::Bool(true).loose_equals(&one).unwrap());
+
+    let a = rt.eval("'abc'").unwrap();
+    let b = rt.eval("'ab' + 'c'").unwrap();
+    assert!(a.strict_equals(&b));
+    let obj = rt.eval("({})").unwrap();
+    assert!(obj.strict_equals(&obj.clone()));
+    assert!(!obj.strict_equals(&rt.eval("({})").unwrap()));
+
+    let throws = rt
+        .eval("({ valueOf() { throw new Error('no') } })")
+        .unwrap();
+    assert!(throws.loose_equals(&one).is_err());
+}
+
+#[test]
+fn same_value_semantics() {
+    let rt = Runtime::new().unwrap();
+    let nan = Value::Number(f64::NAN);
+    assert!(nan.same_value(&nan));
+    assert!(nan.same_value_zero(&nan));
+
+    let zero = Value::Number(0.0);
+    let neg_zero = rt.eval("-0").unwrap();
+    assert!(!zero.same_value(&neg_zero));
+    assert!(zero.same_value_zero(&neg_zero));
+    assert!(zero.strict_equals(&neg_zero));
+
+    let same: bool = rt
+        .eval_as("Object.is(NaN, NaN) && !Object.is(0, -0)")
+        .unwrap();
+    assert!(same);
+    let s = rt.eval("'k'").unwrap();
+    assert!(s.same_value(&rt.eval("'k'").unwrap()));
+}
+
+#[test]
+fn deep_equal_compares_structure() {
+    let rt = Runtime::new().unwrap();
+    let js = rt.eval("({ a: [1, 2] })").unwrap();
+
+    let built = Object::new(&rt).unwrap();
+    let array = Array::new(&rt, 2).unwrap();
+    array.set(0, Value::Number(1.0)).unwrap();
+    array.set(1, Value::Number(2.0)).unwrap();
+    built.set("a", array.into()).unwrap();
+    let built = Value::from(built);
+
+    assert!(js.deep_equal(&built).unwrap());
+    assert!(!js.deep_equal(&rt.eval("({ a: [1, 3] })").unwrap()).unwrap());
+    assert!(!js
+        .deep_equal(&rt.eval("({ a: [1, 2], b: 1 })").unwrap())
+        .unwrap());
+    assert!(!js
+        .deep_equal(&rt.eval("({ a: { 0: 1, 1: 2 } })").unwrap())
+        .unwrap());
+    assert!(rt
+        .eval("({ b: 'x', a: NaN })")
+        .unwrap()
+        .deep_equal(&rt.eval("({ a: NaN, b: 'x' })").unwrap())
+        .unwrap());
+
+    let cycles: Vec<Value> = rt
+        .eval_as("[1, 2].map(function () { var o = { n: 1 }; o.self = o; return o; })")
+        .unwrap();
+    assert!(cycles[0].deep_equal(&cycles[1]).unwrap());
+}